@@ -1,5 +1,6 @@
 use crate::commands::config::model::Config;
-use crate::core::globals::APP_NAME;
+use crate::commands::hosts::rewrite::REWRITE_OFF;
+use crate::core::globals::{APP_NAME, DEFAULT_HOSTS_FILE_PATH};
 use crate::core::i18n::t;
 use crate::core::table::{add_table_row, create_config_table, print_table, set_table_header};
 use anyhow::Result;
@@ -43,6 +44,99 @@ pub fn show() -> Result<()> {
             config.lang.clone(),
         ],
     );
+    add_table_row(
+        &mut table,
+        vec![
+            "hosts_file".to_string(),
+            config
+                .hosts_file
+                .clone()
+                .unwrap_or_else(|| DEFAULT_HOSTS_FILE_PATH.to_string()),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "hosts_rewrite_ip".to_string(),
+            config
+                .hosts_rewrite_ip
+                .clone()
+                .unwrap_or_else(|| REWRITE_OFF.to_string()),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "hosts_ipv6_twin".to_string(),
+            config.hosts_ipv6_twin.to_string(),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "hosts_update_concurrency".to_string(),
+            config.update_concurrency().to_string(),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "hosts_validation".to_string(),
+            config.hosts_validation.to_string(),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "hosts_min_valid_ratio".to_string(),
+            config.get_field("hosts_min_valid_ratio")?,
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "protected_hosts".to_string(),
+            config.protected_hosts().join(", "),
+        ],
+    );
+    // 显示实际生效的 HTTP 设置（包括环境变量的覆盖）
+    let http = config.http.client_options()?;
+    for (field, value) in [
+        ("http.proxy", http.proxy.unwrap_or_default()),
+        ("http.no_proxy", http.no_proxy.unwrap_or_default()),
+        (
+            "http.ca_bundle",
+            http.ca_bundle
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+        ),
+        ("http.timeout_secs", http.timeout.as_secs().to_string()),
+        ("http.user_agent", http.user_agent),
+    ] {
+        add_table_row(&mut table, vec![field.to_string(), value]);
+    }
+    let retry = http.retry;
+    add_table_row(
+        &mut table,
+        vec![
+            "http.retries".to_string(),
+            retry.max_retries.to_string(),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "http.retry_delay_ms".to_string(),
+            retry.base_delay.as_millis().to_string(),
+        ],
+    );
+    add_table_row(
+        &mut table,
+        vec![
+            "http.max_size_mb".to_string(),
+            (http.max_size / 1024 / 1024).to_string(),
+        ],
+    );
     print_table(&table);
 
     Ok(())
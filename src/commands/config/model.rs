@@ -1,15 +1,275 @@
 use crate::commands::config::file::get_config_path;
-use crate::core::globals::{DEFAULT_LANGUAGE, ZDOCS_PATH};
+use crate::commands::hosts::format::ListFormat;
+use crate::commands::hosts::helpers::get_current_timestamp;
+use crate::commands::hosts::protect::{default_protected_hosts, parse_protected_hosts};
+use crate::commands::hosts::rewrite::parse_rewrite_target;
+use crate::commands::hosts::validation::ValidationLevel;
+use crate::core::globals::{
+    DEFAULT_LANGUAGE, DEFAULT_UPDATE_CONCURRENCY, HOSTS_DEFAULT_MIN_VALID_RATIO,
+    HTTP_CA_BUNDLE_ENV_VARS, HTTP_DEFAULT_MAX_SIZE_MB, HTTP_NO_PROXY_ENV_VARS, HTTP_PROXY_ENV_VARS,
+    HTTP_TIMEOUT_ENV_VAR, HTTP_USER_AGENT_ENV_VAR, ZDOCS_PATH,
+};
+use crate::core::http::{HttpOptions, RetryPolicy};
 use crate::core::i18n::{set_language, t, validate_language};
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub draft_path: String,
     pub lang: String,
-    pub hosts_subscriptions: Option<Vec<String>>,
+    /// 订阅记录，顺序即优先级
+    ///
+    /// 旧版配置中的 URL 字符串列表在加载时迁移为订阅记录。
+    #[serde(default, deserialize_with = "deserialize_subscriptions")]
+    pub hosts_subscriptions: Vec<SubscriptionRecord>,
+    /// 管理的 hosts 文件路径，未设置时使用 /etc/hosts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosts_file: Option<String>,
+    /// 旧版配置中以订阅 URL 为键的附加选项，加载时合并到订阅记录中
+    #[serde(default, skip_serializing)]
+    hosts_subscription_options: BTreeMap<String, SubscriptionOptions>,
+    /// 订阅中黑洞地址条目的默认重写目标（如 0.0.0.0、127.0.0.1、::），未设置时不重写
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosts_rewrite_ip: Option<String>,
+    /// 是否默认为每个 IPv4 黑洞条目额外生成 IPv6 条目
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hosts_ipv6_twin: bool,
+    /// 更新订阅时的最大并发下载数，未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosts_update_concurrency: Option<usize>,
+    /// 下载内容的验证级别
+    #[serde(default, skip_serializing_if = "ValidationLevel::is_standard")]
+    pub hosts_validation: ValidationLevel,
+    /// 下载内容中有效行的最低比例（0 到 1），未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hosts_min_valid_ratio: Option<f64>,
+    /// 订阅不能覆盖的主机名（glob 或 `re:` 正则表达式），未设置时使用默认列表
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protected_hosts: Option<Vec<String>>,
+    /// 下载订阅时的 HTTP 设置
+    #[serde(default, skip_serializing_if = "HttpConfig::is_default")]
+    pub http: HttpConfig,
+}
+
+/// `[http]` 配置段
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct HttpConfig {
+    /// 代理地址（如 `http://proxy.example.com:8080`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// 不经过代理的主机列表（逗号分隔）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// 额外信任的 CA 证书文件（PEM 格式）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<String>,
+    /// 单个请求的超时时间（秒），未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// 请求使用的用户代理，未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// 请求失败后的最大重试次数，未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    /// 首次重试前的等待时间（毫秒），之后每次翻倍，未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_delay_ms: Option<u64>,
+    /// 单个订阅下载内容（解压后）的大小上限（MiB），未设置时使用默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
+
+impl HttpConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 生成 HTTP 客户端设置，环境变量（`HTTPS_PROXY`、`NO_PROXY`、`SSL_CERT_FILE` 等）优先于配置
+    pub fn client_options(&self) -> Result<HttpOptions> {
+        let default = HttpOptions::default();
+        // 超时为 0 时 reqwest 会立即超时，与配置项一样视为未设置
+        let timeout_secs = match env_value(&[HTTP_TIMEOUT_ENV_VAR]) {
+            Some(value) => parse_optional_number(&value)?,
+            None => self.timeout_secs,
+        }
+        .filter(|secs| *secs > 0);
+
+        Ok(HttpOptions {
+            proxy: env_value(HTTP_PROXY_ENV_VARS).or_else(|| self.proxy.clone()),
+            no_proxy: env_value(HTTP_NO_PROXY_ENV_VARS).or_else(|| self.no_proxy.clone()),
+            ca_bundle: env_value(HTTP_CA_BUNDLE_ENV_VARS)
+                .or_else(|| self.ca_bundle.clone())
+                .map(PathBuf::from),
+            timeout: timeout_secs
+                .map(Duration::from_secs)
+                .unwrap_or(default.timeout),
+            user_agent: env_value(&[HTTP_USER_AGENT_ENV_VAR])
+                .or_else(|| self.user_agent.clone())
+                .unwrap_or(default.user_agent),
+            retry: self.retry_policy(),
+            max_size: self
+                .max_size_mb
+                .map(|mb| mb.saturating_mul(1024 * 1024))
+                .unwrap_or(default.max_size),
+        })
+    }
+
+    /// 根据配置生成重试策略
+    pub fn retry_policy(&self) -> RetryPolicy {
+        let default = RetryPolicy::default();
+        RetryPolicy {
+            max_retries: self.retries.unwrap_or(default.max_retries),
+            base_delay: self
+                .retry_delay_ms
+                .map(Duration::from_millis)
+                .unwrap_or(default.base_delay),
+        }
+    }
+}
+
+/// 单个订阅的附加选项
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SubscriptionOptions {
+    /// 订阅名称，可代替 URL 或序号指定订阅
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 订阅标签，用于批量选择订阅（如 `hosts update --tag work`）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 订阅列表的格式，默认根据内容自动检测
+    #[serde(default, skip_serializing_if = "ListFormat::is_auto")]
+    pub format: ListFormat,
+    /// 是否已禁用（保留订阅，但其条目在 hosts 文件中被注释掉）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// 临时禁用的截止时间（UTC 时间戳），到期后下次运行时自动启用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_until: Option<u64>,
+    /// 只保留匹配这些模式的主机名（glob，或以 `re:` 开头的正则表达式），为空时不限制
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// 移除匹配这些模式的主机名
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// 黑洞地址条目的重写目标（IP 或 `off`），未设置时使用全局默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewrite_ip: Option<String>,
+    /// 是否额外生成 IPv6 条目，未设置时使用全局默认值
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_twin: Option<bool>,
+    /// 固定的内容 SHA-256（小写十六进制），下载的内容不一致时更新失败
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// 验证分离签名的公钥（minisign 公钥或 base64 编码的 ed25519 公钥）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// 签名文件地址，未设置时使用订阅 URL 加 `.minisig` 或 `.sig` 后缀
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_url: Option<String>,
+    /// 上次下载响应的 ETag，用于条件请求
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// 上次下载响应的 Last-Modified，用于条件请求
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+/// 订阅记录：订阅 URL、附加选项和更新状态
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubscriptionRecord {
+    pub url: String,
+    /// 添加订阅的时间（UTC 时间戳），从旧版配置迁移的订阅没有记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added_at: Option<u64>,
+    /// 上次成功更新的时间（UTC 时间戳）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<u64>,
+    /// 上次更新失败的错误信息，更新成功后清除
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// 上次更新后订阅块中的条目数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_count: Option<usize>,
+    /// 备注
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(flatten)]
+    pub options: SubscriptionOptions,
+    /// 是否从旧版配置的 URL 字符串迁移而来（不保存）
+    #[serde(skip)]
+    legacy: bool,
+}
+
+impl SubscriptionRecord {
+    /// 新添加的订阅记录
+    fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            added_at: Some(get_current_timestamp()),
+            last_updated: None,
+            last_error: None,
+            entry_count: None,
+            notes: None,
+            options: SubscriptionOptions::default(),
+            legacy: false,
+        }
+    }
+
+    /// 记录一次成功的更新，`entry_count` 为订阅块中的条目数
+    pub fn mark_updated(&mut self, entry_count: usize) {
+        self.last_updated = Some(get_current_timestamp());
+        self.last_error = None;
+        self.entry_count = Some(entry_count);
+    }
+
+    /// 记录一次失败的更新，保留上次成功更新的时间和条目数
+    pub fn mark_failed(&mut self, error: &str) {
+        self.last_error = Some(error.to_string());
+    }
+}
+
+/// 配置中的订阅项：旧版的 URL 字符串或订阅记录
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SubscriptionEntry {
+    Url(String),
+    Record(Box<SubscriptionRecord>),
+}
+
+/// 读取订阅列表，兼容旧版的 URL 字符串列表
+fn deserialize_subscriptions<'de, D>(deserializer: D) -> Result<Vec<SubscriptionRecord>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let entries = Vec::<SubscriptionEntry>::deserialize(deserializer)?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| match entry {
+            SubscriptionEntry::Url(url) => SubscriptionRecord {
+                added_at: None,
+                legacy: true,
+                ..SubscriptionRecord::new(&url)
+            },
+            SubscriptionEntry::Record(record) => *record,
+        })
+        .collect())
+}
+
+impl SubscriptionOptions {
+    /// 清除缓存验证信息，下次更新时强制重新下载
+    ///
+    /// 过滤或重写规则变化后调用，确保新规则应用到最新的内容上。
+    pub fn clear_fetch_state(&mut self) {
+        self.etag = None;
+        self.last_modified = None;
+    }
 }
 
 impl Default for Config {
@@ -17,7 +277,16 @@ impl Default for Config {
         Self {
             draft_path: ZDOCS_PATH.to_string(),
             lang: DEFAULT_LANGUAGE.to_string(),
-            hosts_subscriptions: Some(Vec::new()),
+            hosts_subscriptions: Vec::new(),
+            hosts_file: None,
+            hosts_subscription_options: BTreeMap::new(),
+            hosts_rewrite_ip: None,
+            hosts_ipv6_twin: false,
+            hosts_update_concurrency: None,
+            hosts_validation: ValidationLevel::default(),
+            hosts_min_valid_ratio: None,
+            protected_hosts: None,
+            http: HttpConfig::default(),
         }
     }
 }
@@ -40,9 +309,9 @@ impl Config {
         let mut config: Config = toml::from_str(&content)
             .with_context(|| t!("error.config_parse_failed").to_string())?;
 
-        // 兼容性处理：如果 hosts_subscriptions 字段不存在，初始化为空列表
-        if config.hosts_subscriptions.is_none() {
-            config.hosts_subscriptions = Some(Vec::new());
+        // 兼容性处理：将旧版的 URL 列表和附加选项迁移为订阅记录并写回配置文件
+        if config.migrate_legacy_subscriptions() {
+            config.save()?;
         }
 
         // 设置语言
@@ -57,6 +326,19 @@ impl Config {
         Ok(config)
     }
 
+    /// 将旧版配置的订阅 URL 和以 URL 为键的附加选项合并为订阅记录，返回是否有迁移
+    fn migrate_legacy_subscriptions(&mut self) -> bool {
+        let legacy_options = std::mem::take(&mut self.hosts_subscription_options);
+        let mut migrated = !legacy_options.is_empty();
+        for record in &mut self.hosts_subscriptions {
+            if let Some(options) = legacy_options.get(&record.url) {
+                record.options = options.clone();
+            }
+            migrated |= std::mem::take(&mut record.legacy);
+        }
+        migrated
+    }
+
     pub fn save(&self) -> Result<()> {
         let config_path = get_config_path()?;
 
@@ -88,55 +370,285 @@ impl Config {
                 // 立即应用语言设置
                 set_language(value)?;
             }
+            "hosts_file" => {
+                // 空值表示恢复默认的 /etc/hosts
+                let value = value.trim();
+                self.hosts_file = if value.is_empty() {
+                    None
+                } else {
+                    Some(value.to_string())
+                };
+            }
+            "hosts_rewrite_ip" => {
+                // 空值表示不重写
+                let value = value.trim();
+                self.hosts_rewrite_ip = if value.is_empty() {
+                    None
+                } else {
+                    parse_rewrite_target(value)?.map(|ip| ip.to_string())
+                };
+                self.clear_all_fetch_state();
+            }
+            "hosts_ipv6_twin" => {
+                self.hosts_ipv6_twin = value.trim().parse().map_err(|_| {
+                    anyhow::anyhow!("{}", t!("error.invalid_bool_value", value = value))
+                })?;
+                self.clear_all_fetch_state();
+            }
+            "http.proxy" => {
+                self.http.proxy = optional_string(value);
+                if let Some(proxy) = &self.http.proxy {
+                    reqwest::Proxy::all(proxy).map_err(|e| {
+                        anyhow::anyhow!(
+                            "{}",
+                            t!("error.http_invalid_proxy", proxy = proxy, error = e)
+                        )
+                    })?;
+                }
+            }
+            "http.no_proxy" => {
+                self.http.no_proxy = optional_string(value);
+            }
+            "http.ca_bundle" => {
+                self.http.ca_bundle = optional_string(value);
+                if let Some(path) = &self.http.ca_bundle
+                    && !std::path::Path::new(path).is_file()
+                {
+                    anyhow::bail!("{}", t!("error.http_ca_bundle_not_found", path = path));
+                }
+            }
+            "http.timeout_secs" => {
+                self.http.timeout_secs = parse_optional_number(value)?.filter(|secs| *secs > 0);
+            }
+            "http.user_agent" => {
+                self.http.user_agent = optional_string(value);
+            }
+            "http.retries" => {
+                self.http.retries = parse_optional_number(value)?;
+            }
+            "http.retry_delay_ms" => {
+                self.http.retry_delay_ms = parse_optional_number(value)?;
+            }
+            "http.max_size_mb" => {
+                self.http.max_size_mb = parse_optional_number(value)?.filter(|mb| *mb > 0);
+            }
+            "hosts_update_concurrency" => {
+                // 空值表示使用默认值
+                let value = value.trim();
+                self.hosts_update_concurrency = if value.is_empty() {
+                    None
+                } else {
+                    match value.parse::<usize>() {
+                        Ok(concurrency) if concurrency > 0 => Some(concurrency),
+                        _ => anyhow::bail!(
+                            "{}",
+                            t!("error.invalid_concurrency_value", value = value)
+                        ),
+                    }
+                };
+            }
+            "hosts_validation" => {
+                // 空值表示恢复默认级别
+                let value = value.trim();
+                self.hosts_validation = if value.is_empty() {
+                    ValidationLevel::default()
+                } else {
+                    value.parse()?
+                };
+            }
+            "hosts_min_valid_ratio" => {
+                self.hosts_min_valid_ratio = parse_optional_number::<f64>(value)?;
+                if let Some(ratio) = self.hosts_min_valid_ratio
+                    && !(0.0..=1.0).contains(&ratio)
+                {
+                    anyhow::bail!("{}", t!("error.invalid_ratio_value", value = value));
+                }
+            }
+            "protected_hosts" => {
+                // 空值表示恢复默认列表
+                let hosts = parse_protected_hosts(value)?;
+                self.protected_hosts = if hosts.is_empty() { None } else { Some(hosts) };
+                self.clear_all_fetch_state();
+            }
             _ => anyhow::bail!(t!("error.unknown_field", field = field).to_string()),
         }
         Ok(())
     }
 
+    /// 实际生效的受保护主机名
+    pub fn protected_hosts(&self) -> Vec<String> {
+        self.protected_hosts
+            .clone()
+            .unwrap_or_else(default_protected_hosts)
+    }
+
+    /// 更新订阅时的最大并发下载数
+    pub fn update_concurrency(&self) -> usize {
+        self.hosts_update_concurrency
+            .unwrap_or(DEFAULT_UPDATE_CONCURRENCY)
+    }
+
     // Hosts 订阅管理方法
     pub fn add_hosts_subscription(&mut self, url: &str) -> Result<bool> {
-        if self.hosts_subscriptions.is_none() {
-            self.hosts_subscriptions = Some(Vec::new());
-        }
-
-        let subscriptions = self
-            .hosts_subscriptions
-            .as_mut()
-            .ok_or_else(|| anyhow::anyhow!("{}", t!("error.config_invalid_state")))?;
-        if subscriptions.contains(&url.to_string()) {
+        if self.get_subscription_record(url).is_some() {
             return Ok(false); // 已存在
         }
 
-        subscriptions.push(url.to_string());
+        self.hosts_subscriptions.push(SubscriptionRecord::new(url));
         Ok(true) // 添加成功
     }
 
     pub fn remove_hosts_subscription(&mut self, url: &str) -> Result<bool> {
-        if let Some(subscriptions) = &mut self.hosts_subscriptions
-            && let Some(pos) = subscriptions.iter().position(|x| x == url)
-        {
-            subscriptions.remove(pos);
+        if let Some(pos) = self.subscription_position(url) {
+            self.hosts_subscriptions.remove(pos);
             return Ok(true); // 删除成功
         }
         Ok(false) // 未找到
     }
 
+    /// 将订阅移动到指定位置（从 0 开始，超出范围时放到末尾），位置即优先级
+    pub fn move_hosts_subscription(&mut self, url: &str, position: usize) -> Result<bool> {
+        if let Some(pos) = self.subscription_position(url) {
+            let item = self.hosts_subscriptions.remove(pos);
+            let position = position.min(self.hosts_subscriptions.len());
+            self.hosts_subscriptions.insert(position, item);
+            return Ok(true); // 移动成功
+        }
+        Ok(false) // 未找到
+    }
+
     pub fn get_hosts_subscriptions(&self) -> Vec<String> {
         self.hosts_subscriptions
-            .as_ref()
-            .unwrap_or(&Vec::new())
-            .to_vec()
+            .iter()
+            .map(|record| record.url.clone())
+            .collect()
+    }
+
+    fn subscription_position(&self, url: &str) -> Option<usize> {
+        self.hosts_subscriptions
+            .iter()
+            .position(|record| record.url == url)
+    }
+
+    /// 获取订阅记录
+    pub fn get_subscription_record(&self, url: &str) -> Option<&SubscriptionRecord> {
+        self.hosts_subscriptions
+            .iter()
+            .find(|record| record.url == url)
+    }
+
+    /// 获取订阅记录的可变引用，订阅不存在时返回错误（只有 `add_hosts_subscription` 会添加订阅）
+    pub fn subscription_record_mut(&mut self, url: &str) -> Result<&mut SubscriptionRecord> {
+        let pos = self.subscription_position(url).ok_or_else(|| {
+            anyhow::anyhow!("{}", t!("error.hosts_subscription_not_found", target = url))
+        })?;
+        Ok(&mut self.hosts_subscriptions[pos])
+    }
+
+    /// 获取订阅的附加选项（未设置时返回默认值）
+    pub fn get_subscription_options(&self, url: &str) -> SubscriptionOptions {
+        self.get_subscription_record(url)
+            .map(|record| record.options.clone())
+            .unwrap_or_default()
+    }
+
+    /// 根据名称查找订阅 URL（不区分大小写）
+    pub fn find_subscription_by_name(&self, name: &str) -> Option<String> {
+        self.hosts_subscriptions
+            .iter()
+            .find(|record| {
+                record
+                    .options
+                    .name
+                    .as_deref()
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            })
+            .map(|record| record.url.clone())
+    }
+
+    /// 获取订阅附加选项的可变引用，订阅不存在时返回错误
+    pub fn subscription_options_mut(&mut self, url: &str) -> Result<&mut SubscriptionOptions> {
+        Ok(&mut self.subscription_record_mut(url)?.options)
+    }
+
+    /// 清除所有订阅的缓存验证信息（全局规则变化后调用）
+    fn clear_all_fetch_state(&mut self) {
+        self.hosts_subscriptions
+            .iter_mut()
+            .for_each(|record| record.options.clear_fetch_state());
+    }
+
+    /// 订阅是否已禁用
+    pub fn is_subscription_disabled(&self, url: &str) -> bool {
+        self.get_subscription_options(url).disabled
     }
 
     pub fn get_field(&self, field: &str) -> Result<String> {
         match field {
             "draft_path" => Ok(self.draft_path.clone()),
             "lang" => Ok(self.lang.clone()),
-            "hosts_subscriptions" => Ok(format!(
-                "{:?}",
-                self.hosts_subscriptions.as_ref().unwrap_or(&Vec::new())
-            )),
+            "hosts_file" => Ok(self.hosts_file.clone().unwrap_or_default()),
+            "hosts_rewrite_ip" => Ok(self.hosts_rewrite_ip.clone().unwrap_or_default()),
+            "hosts_ipv6_twin" => Ok(self.hosts_ipv6_twin.to_string()),
+            "hosts_update_concurrency" => Ok(self.update_concurrency().to_string()),
+            "hosts_validation" => Ok(self.hosts_validation.to_string()),
+            "protected_hosts" => Ok(self.protected_hosts().join(", ")),
+            // 与 `set` 接受的格式一致（0 到 1 之间的小数）
+            "hosts_min_valid_ratio" => Ok(self
+                .hosts_min_valid_ratio
+                .unwrap_or(HOSTS_DEFAULT_MIN_VALID_RATIO)
+                .to_string()),
+            "http.proxy" => Ok(self.http.proxy.clone().unwrap_or_default()),
+            "http.no_proxy" => Ok(self.http.no_proxy.clone().unwrap_or_default()),
+            "http.ca_bundle" => Ok(self.http.ca_bundle.clone().unwrap_or_default()),
+            "http.timeout_secs" => Ok(self
+                .http
+                .timeout_secs
+                .unwrap_or(HttpOptions::default().timeout.as_secs())
+                .to_string()),
+            "http.user_agent" => Ok(self
+                .http
+                .user_agent
+                .clone()
+                .unwrap_or(HttpOptions::default().user_agent)),
+            "http.retries" => Ok(self.http.retry_policy().max_retries.to_string()),
+            "http.retry_delay_ms" => {
+                Ok(self.http.retry_policy().base_delay.as_millis().to_string())
+            }
+            "http.max_size_mb" => Ok(self
+                .http
+                .max_size_mb
+                .unwrap_or(HTTP_DEFAULT_MAX_SIZE_MB)
+                .to_string()),
+            "hosts_subscriptions" => Ok(format!("{:?}", self.get_hosts_subscriptions())),
             _ => anyhow::bail!(t!("error.unknown_field", field = field).to_string()),
         }
     }
 }
+
+/// 解析可选的非负整数配置值，空值表示使用默认值
+fn parse_optional_number<T: FromStr>(value: &str) -> Result<Option<T>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| anyhow::anyhow!("{}", t!("error.invalid_number_value", value = value)))
+}
+
+/// 解析可选的字符串配置值，空值表示未设置
+fn optional_string(value: &str) -> Option<String> {
+    Some(value.trim())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+}
+
+/// 按顺序读取环境变量，返回第一个非空值
+fn env_value(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find_map(|value| optional_string(&value))
+}
@@ -1,9 +1,13 @@
-use crate::commands::hosts::{create_hosts_manager, helpers::generate_backup_filename};
+use crate::commands::hosts::{
+    create_hosts_manager,
+    helpers::{ensure_hosts_write_permission, generate_backup_filename},
+};
+use crate::core::filesystem::StructuredFileManager;
+use crate::core::globals::{BACKUP_FILE_PREFIX, BACKUP_FILE_SUFFIX};
 use crate::core::i18n::t;
-use crate::core::permission::ensure_sudo_privileges;
 use crate::core::table::{add_table_row, create_backup_table, print_table, set_table_header};
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// 处理备份命令
 pub fn handle_backup() -> Result<()> {
@@ -58,7 +62,7 @@ pub fn handle_restore(backup_file: Option<&str>) -> Result<()> {
     println!("{}", t!("command.hosts.restore.starting"));
 
     // 权限检查
-    ensure_sudo_privileges()?;
+    ensure_hosts_write_permission()?;
 
     let hosts_manager = create_hosts_manager()?;
 
@@ -173,56 +177,81 @@ pub fn attempt_hosts_rollback() -> Result<()> {
     Ok(())
 }
 
-/// 显示可用的备份文件
-fn display_available_backups() -> Result<()> {
-    println!("{}", t!("command.hosts.backup_list.title"));
+/// 从备份文件名中解析时间戳
+fn backup_timestamp(path: &Path) -> Option<u64> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| {
+            name.strip_prefix(BACKUP_FILE_PREFIX)
+                .and_then(|s| s.strip_suffix(BACKUP_FILE_SUFFIX))
+                .and_then(|s| s.parse::<u64>().ok())
+        })
+}
 
-    let hosts_manager = create_hosts_manager()?;
-    let backup_dir = &hosts_manager.file_manager().backup_dir;
+/// 列出当前目标文件的所有备份，最新的在前（与备份列表中的序号一致）
+pub fn list_hosts_backups(hosts_manager: &StructuredFileManager) -> Result<Vec<PathBuf>> {
+    let mut backups: Vec<PathBuf> = hosts_manager
+        .file_manager()
+        .list_backups()?
+        .into_iter()
+        .filter(|path| backup_timestamp(path).is_some())
+        .collect();
 
-    if !backup_dir.exists() {
-        println!("{}", t!("command.hosts.backup_list.empty"));
-        return Ok(());
+    backups.sort_by_key(|path| std::cmp::Reverse(backup_timestamp(path).unwrap_or(0)));
+    Ok(backups)
+}
+
+/// 根据文件名、备份列表中的序号（1 为最新）或路径查找备份文件
+pub fn resolve_backup(hosts_manager: &StructuredFileManager, selector: &str) -> Result<PathBuf> {
+    let backups = list_hosts_backups(hosts_manager)?;
+
+    if let Some(path) = backups
+        .iter()
+        .find(|path| path.file_name().and_then(|n| n.to_str()) == Some(selector))
+    {
+        return Ok(path.clone());
     }
 
-    let entries = std::fs::read_dir(backup_dir)?;
-    let mut backups: Vec<std::fs::DirEntry> = entries
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| {
-            if let Some(name) = entry.file_name().to_str() {
-                name.starts_with("hosts_backup_") && name.ends_with(".txt")
-            } else {
-                false
-            }
-        })
-        .collect();
+    if let Some(path) = selector
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index >= 1)
+        .and_then(|index| backups.get(index - 1))
+    {
+        return Ok(path.clone());
+    }
+
+    let path = PathBuf::from(selector);
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    anyhow::bail!(
+        "{}",
+        t!("error.hosts_backup_file_not_exist", path = selector)
+    )
+}
+
+/// 显示可用的备份文件
+pub fn display_available_backups() -> Result<()> {
+    println!("{}", t!("command.hosts.backup_list.title"));
+
+    let hosts_manager = create_hosts_manager()?;
+    let backups = list_hosts_backups(&hosts_manager)?;
 
     if backups.is_empty() {
         println!("{}", t!("command.hosts.backup_list.empty"));
         return Ok(());
     }
 
-    // 按时间戳排序
-    backups.sort_by_key(|entry| {
-        entry
-            .file_name()
-            .to_str()
-            .and_then(|name| {
-                name.strip_prefix("hosts_backup_")
-                    .and_then(|s| s.strip_suffix(".txt"))
-                    .and_then(|s| s.parse::<u64>().ok())
-            })
-            .unwrap_or(0)
-    });
-
     let total_backups = backups.len();
-    backups.reverse();
     let show_count = std::cmp::min(10, backups.len());
 
     let mut table = create_backup_table();
     set_table_header(
         &mut table,
         vec![
+            "序号".to_string(),
             "标记".to_string(),
             "文件名".to_string(),
             "时间戳".to_string(),
@@ -230,19 +259,15 @@ fn display_available_backups() -> Result<()> {
         ],
     );
 
-    for (i, entry) in backups.iter().enumerate().take(show_count) {
-        let file_path = entry.path();
-        if let Some(file_name) = entry.file_name().to_str() {
-            let timestamp = file_name
-                .strip_prefix("hosts_backup_")
-                .and_then(|s| s.strip_suffix(".txt"))
-                .and_then(|s| s.parse::<u64>().ok())
-                .unwrap_or(0);
-            let size = std::fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+    for (i, file_path) in backups.iter().enumerate().take(show_count) {
+        if let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) {
+            let timestamp = backup_timestamp(file_path).unwrap_or(0);
+            let size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
             let marker = if i == 0 { "🔸最新" } else { "📄" };
             add_table_row(
                 &mut table,
                 vec![
+                    (i + 1).to_string(),
                     marker.to_string(),
                     file_name.to_string(),
                     timestamp.to_string(),
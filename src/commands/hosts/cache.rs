@@ -0,0 +1,228 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{
+    entry::EntrySource,
+    format::ListFormat,
+    helpers::{format_duration, get_current_timestamp, resolve_subscription_target},
+};
+use crate::core::globals::{APP_NAME, HOSTS_CACHE_DIR_NAME};
+use crate::core::i18n::t;
+use crate::core::table::{add_table_row, create_subscription_table, print_table, set_table_header};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// 原始内容缓存文件的扩展名
+const RAW_EXTENSION: &str = "raw";
+/// 标准化内容（每行一个标准格式条目）缓存文件的扩展名
+const NORMALIZED_EXTENSION: &str = "hosts";
+/// 缓存元数据文件的扩展名
+const METADATA_EXTENSION: &str = "toml";
+
+/// 单个订阅缓存的元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheMetadata {
+    pub url: String,
+    /// 下载时间（UTC 时间戳）
+    pub fetched_at: u64,
+    /// 原始内容大小（字节）
+    pub raw_size: usize,
+    /// 标准化后的条目数量
+    pub entry_count: usize,
+}
+
+/// 订阅内容缓存目录
+fn cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("{}", t!("error.hosts_cache_dir_failed")))?;
+    Ok(cache_dir.join(APP_NAME).join(HOSTS_CACHE_DIR_NAME))
+}
+
+/// 订阅缓存文件的路径，文件名由 URL 的哈希生成
+fn cache_path(url: &str, extension: &str) -> Result<PathBuf> {
+    let digest = format!("{:x}", Sha256::digest(url.as_bytes()));
+    Ok(cache_dir()?.join(format!("{}.{extension}", &digest[..16])))
+}
+
+/// 保存订阅下载的原始字节和解码后的 `content` 按 `format` 转换得到的标准化内容
+///
+/// 原始字节即发布的文件本身（未解压），`hosts pin` 据此计算校验和。
+pub fn store(url: &str, raw: &[u8], content: &str, format: ListFormat) -> Result<()> {
+    let dir = cache_dir()?;
+    fs::create_dir_all(&dir)
+        .with_context(|| t!("error.hosts_cache_write_failed", path = dir.display()).to_string())?;
+
+    let (entries, _) = format.convert(content, &EntrySource::Subscription(url.to_string()));
+    let normalized: String = entries.iter().map(|entry| entry.to_line() + "\n").collect();
+    let metadata = CacheMetadata {
+        url: url.to_string(),
+        fetched_at: get_current_timestamp(),
+        raw_size: raw.len(),
+        entry_count: entries.len(),
+    };
+
+    let files = [
+        (RAW_EXTENSION, raw.to_vec()),
+        (NORMALIZED_EXTENSION, normalized.into_bytes()),
+        (
+            METADATA_EXTENSION,
+            toml::to_string_pretty(&metadata)?.into_bytes(),
+        ),
+    ];
+    for (extension, content) in files {
+        let path = cache_path(url, extension)?;
+        fs::write(&path, content).with_context(|| {
+            t!("error.hosts_cache_write_failed", path = path.display()).to_string()
+        })?;
+    }
+
+    Ok(())
+}
+
+/// 保存订阅内容到缓存，失败时只给出提示（缓存不影响订阅更新）
+pub fn store_or_warn(url: &str, raw: &[u8], content: &str, format: ListFormat) {
+    if let Err(e) = store(url, raw, content, format) {
+        println!("{}", t!("command.hosts.cache.store_failed", error = e));
+    }
+}
+
+/// 读取订阅的标准化缓存内容，没有缓存时返回 None
+pub fn load_normalized(url: &str) -> Result<Option<String>> {
+    Ok(load(url, NORMALIZED_EXTENSION)?.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// 读取订阅下载的原始字节（未解压、未解码），没有缓存时返回 None
+pub fn load_raw(url: &str) -> Result<Option<Vec<u8>>> {
+    load(url, RAW_EXTENSION)
+}
+
+/// 读取指定类型的缓存文件
+fn load(url: &str, extension: &str) -> Result<Option<Vec<u8>>> {
+    let path = cache_path(url, extension)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    fs::read(&path)
+        .map(Some)
+        .with_context(|| t!("error.hosts_cache_read_failed", path = path.display()).to_string())
+}
+
+/// 读取订阅的标准化缓存内容，没有缓存时报错
+pub fn require_normalized(url: &str) -> Result<String> {
+    load_normalized(url)?
+        .ok_or_else(|| anyhow::anyhow!("{}", t!("error.hosts_cache_missing", url = url)))
+}
+
+/// 删除订阅的缓存，返回是否存在缓存
+pub fn remove(url: &str) -> Result<bool> {
+    let mut removed = false;
+    for extension in [RAW_EXTENSION, NORMALIZED_EXTENSION, METADATA_EXTENSION] {
+        let path = cache_path(url, extension)?;
+        if path.exists() {
+            fs::remove_file(&path)?;
+            removed = true;
+        }
+    }
+    Ok(removed)
+}
+
+/// 列出所有订阅缓存，按 URL 排序
+pub fn list() -> Result<Vec<CacheMetadata>> {
+    let dir = cache_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut caches: Vec<CacheMetadata> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(METADATA_EXTENSION))
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .filter_map(|content| toml::from_str(&content).ok())
+        .collect();
+    caches.sort_by(|a, b| a.url.cmp(&b.url));
+
+    Ok(caches)
+}
+
+/// 处理缓存列表命令
+pub fn handle_cache_list() -> Result<()> {
+    println!(
+        "{}",
+        t!("command.hosts.cache.title", path = cache_dir()?.display())
+    );
+    println!();
+
+    let caches = list()?;
+    if caches.is_empty() {
+        println!("{}", t!("command.hosts.cache.empty"));
+        return Ok(());
+    }
+
+    let subscriptions = Config::load()?.get_hosts_subscriptions();
+    let now = get_current_timestamp();
+    let mut table = create_subscription_table();
+    set_table_header(
+        &mut table,
+        vec![
+            t!("command.hosts.list.table_header_url").to_string(),
+            t!("command.hosts.cache.table_header_fetched_at").to_string(),
+            t!("command.hosts.cache.table_header_size").to_string(),
+            t!("command.hosts.cache.table_header_entries").to_string(),
+            t!("command.hosts.list.table_header_status").to_string(),
+        ],
+    );
+    for cache in &caches {
+        let status = if subscriptions.contains(&cache.url) {
+            t!("command.hosts.cache.status_subscribed")
+        } else {
+            t!("command.hosts.cache.status_orphaned")
+        };
+        add_table_row(
+            &mut table,
+            vec![
+                cache.url.clone(),
+                t!(
+                    "command.hosts.cache.fetched_ago",
+                    duration = format_duration(now.saturating_sub(cache.fetched_at))
+                )
+                .to_string(),
+                cache.raw_size.to_string(),
+                cache.entry_count.to_string(),
+                status.to_string(),
+            ],
+        );
+    }
+    print_table(&table);
+
+    println!(
+        "{}",
+        t!("command.hosts.cache.statistics", count = caches.len())
+    );
+    Ok(())
+}
+
+/// 处理缓存清理命令，未指定订阅时清除所有缓存
+pub fn handle_cache_clear(target: Option<&str>) -> Result<()> {
+    let count = match target {
+        Some(target) => {
+            // 已取消的订阅也可以按 URL 清除
+            let url = resolve_subscription_target(&Config::load()?, target)
+                .unwrap_or_else(|| target.to_string());
+            usize::from(remove(&url)?)
+        }
+        None => {
+            let count = list()?.len();
+            let dir = cache_dir()?;
+            if dir.exists() {
+                fs::remove_dir_all(&dir)?;
+            }
+            count
+        }
+    };
+
+    println!("{}", t!("command.hosts.cache.cleared", count = count));
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::commands::hosts::{core::HostsFileStructure, create_hosts_manager, entry::HostEntry};
+use crate::core::i18n::t;
+use crate::core::table::{add_table_row, create_subscription_table, print_table, set_table_header};
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// 冲突提示中最多列出的主机名数量
+const MAX_CONFLICT_WARNINGS: usize = 10;
+
+/// 同一主机名在同一地址族下存在多个不同 IP 的映射
+#[derive(Debug, Clone)]
+pub struct HostConflict {
+    /// 小写的主机名
+    pub hostname: String,
+    /// 所有映射，按文件顺序排列，第一条生效
+    pub mappings: Vec<HostEntry>,
+}
+
+impl HostConflict {
+    /// 冲突的唯一标识（主机名 + 地址族）
+    fn key(&self) -> (String, bool) {
+        (
+            self.hostname.clone(),
+            self.mappings
+                .first()
+                .is_some_and(|entry| entry.ip.is_ipv4()),
+        )
+    }
+}
+
+/// 查找 hosts 文件中所有存在冲突的主机名
+///
+/// IPv4 和 IPv6 分开比较，例如 `127.0.0.1 localhost` 与 `::1 localhost` 不算冲突。
+pub fn find_conflicts(structure: &HostsFileStructure) -> Vec<HostConflict> {
+    let mut groups: Vec<HostConflict> = Vec::new();
+    let mut index: HashMap<(String, bool), usize> = HashMap::new();
+
+    for entry in structure.entries() {
+        for hostname in entry.hostnames() {
+            let key = (hostname.to_lowercase(), entry.ip.is_ipv4());
+            let position = *index.entry(key.clone()).or_insert_with(|| {
+                groups.push(HostConflict {
+                    hostname: key.0.clone(),
+                    mappings: Vec::new(),
+                });
+                groups.len() - 1
+            });
+            groups[position].mappings.push(entry.clone());
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|group| {
+            group
+                .mappings
+                .iter()
+                .any(|entry| entry.ip != group.mappings[0].ip)
+        })
+        .collect()
+}
+
+/// 处理冲突检查命令
+pub fn handle_conflicts() -> Result<()> {
+    println!("{}", t!("command.hosts.conflicts.title"));
+    println!();
+
+    let hosts_manager = create_hosts_manager()?;
+    let structure: HostsFileStructure = hosts_manager.parse_file()?;
+    let conflicts = find_conflicts(&structure);
+
+    if conflicts.is_empty() {
+        println!("{}", t!("command.hosts.conflicts.empty"));
+        return Ok(());
+    }
+
+    let mut table = create_subscription_table();
+    set_table_header(
+        &mut table,
+        vec![
+            t!("command.hosts.conflicts.table_header_hostname").to_string(),
+            t!("command.hosts.search.table_header_ip").to_string(),
+            t!("command.hosts.search.table_header_source").to_string(),
+            t!("command.hosts.conflicts.table_header_result").to_string(),
+        ],
+    );
+    for conflict in &conflicts {
+        for (i, entry) in conflict.mappings.iter().enumerate() {
+            let result = if i == 0 {
+                t!("command.hosts.conflicts.winner").to_string()
+            } else {
+                t!("command.hosts.conflicts.shadowed").to_string()
+            };
+            let hostname = if i == 0 {
+                conflict.hostname.clone()
+            } else {
+                String::new()
+            };
+            add_table_row(
+                &mut table,
+                vec![
+                    hostname,
+                    entry.ip.to_string(),
+                    entry.source.to_string(),
+                    result,
+                ],
+            );
+        }
+    }
+    print_table(&table);
+
+    println!(
+        "{}",
+        t!(
+            "command.hosts.conflicts.statistics",
+            count = conflicts.len()
+        )
+    );
+    println!("{}", t!("command.hosts.conflicts.priority_hint"));
+
+    Ok(())
+}
+
+/// 读取当前 hosts 文件中的冲突（读取失败时视为无冲突）
+pub fn current_conflicts() -> Vec<HostConflict> {
+    create_hosts_manager()
+        .and_then(|manager| manager.parse_file::<HostsFileStructure>())
+        .map(|structure| find_conflicts(&structure))
+        .unwrap_or_default()
+}
+
+/// 对比更新前后的冲突，提示新增的冲突
+pub fn warn_new_conflicts(before: &[HostConflict], after: &[HostConflict]) {
+    let new_conflicts: Vec<&HostConflict> = after
+        .iter()
+        .filter(|conflict| !before.iter().any(|old| old.key() == conflict.key()))
+        .collect();
+
+    if new_conflicts.is_empty() {
+        return;
+    }
+
+    println!(
+        "{}",
+        t!(
+            "command.hosts.conflicts.new_warning",
+            count = new_conflicts.len()
+        )
+    );
+    for conflict in new_conflicts.iter().take(MAX_CONFLICT_WARNINGS) {
+        let mappings: Vec<String> = conflict
+            .mappings
+            .iter()
+            .map(|entry| format!("{} ({})", entry.ip, entry.source))
+            .collect();
+        println!("   • {}: {}", conflict.hostname, mappings.join(" > "));
+    }
+    if new_conflicts.len() > MAX_CONFLICT_WARNINGS {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.conflicts.more",
+                count = new_conflicts.len() - MAX_CONFLICT_WARNINGS
+            )
+        );
+    }
+    println!("{}", t!("command.hosts.conflicts.check_hint"));
+}
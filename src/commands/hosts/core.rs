@@ -1,12 +1,22 @@
 use anyhow::Result;
 use clap::{Arg, ArgMatches, Command};
-use std::collections::HashMap;
 
+use crate::commands::hosts::entry::{EntrySource, HostEntry};
+use crate::commands::hosts::export::ExportFormat;
+use crate::commands::hosts::format::ListFormat;
+use crate::commands::hosts::helpers::{
+    get_hosts_file_path, is_default_hosts_file, set_hosts_file_override,
+};
 use crate::commands::hosts::{
-    handle_backup, handle_list, handle_restore, handle_subscribe, handle_unsubscribe, handle_update,
+    handle_add, handle_backup, handle_cache_clear, handle_cache_list, handle_conflicts,
+    handle_diff, handle_disable, handle_enable, handle_entries, handle_export, handle_filter_add,
+    handle_filter_list, handle_filter_remove, handle_label, handle_list, handle_move, handle_pin,
+    handle_priority, handle_public_key, handle_remove, handle_restore, handle_rewrite,
+    handle_search, handle_subscribe, handle_unsubscribe, handle_update,
 };
 use crate::core::filesystem::FileStructure;
 use crate::core::globals::{
+    HOSTS_DISABLED_LINE_PREFIX, HOSTS_LOCAL_END_MARKER, HOSTS_LOCAL_START_MARKER,
     HOSTS_SUBSCRIPTION_END_MARKER, HOSTS_SUBSCRIPTION_MARKER_SUFFIX,
     HOSTS_SUBSCRIPTION_START_MARKER,
 };
@@ -17,6 +27,13 @@ pub fn register_command(app: &mut Command) {
     *app = app.clone().subcommand(
         Command::new("hosts")
             .about(t!("command.hosts.description").to_string())
+            .arg(
+                Arg::new("hosts_file")
+                    .long("hosts-file")
+                    .value_name("PATH")
+                    .help(t!("help.hosts_file").to_string())
+                    .global(true),
+            )
             .subcommand(
                 Command::new("subscribe")
                     .about(t!("command.hosts.subscribe.description").to_string())
@@ -25,7 +42,34 @@ pub fn register_command(app: &mut Command) {
                             .help(t!("help.hosts_url").to_string())
                             .required(true)
                             .index(1),
-                    ),
+                    )
+                    .arg(
+                        Arg::new("name")
+                            .long("name")
+                            .value_name("NAME")
+                            .help(t!("help.hosts_subscription_name").to_string()),
+                    )
+                    .arg(tag_arg(t!("help.hosts_tag").to_string()))
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(ListFormat::NAMES)
+                            .help(t!("help.hosts_format").to_string()),
+                    )
+                    .arg(
+                        Arg::new("sha256")
+                            .long("sha256")
+                            .value_name("SHA256")
+                            .help(t!("help.hosts_subscribe_sha256").to_string()),
+                    )
+                    .arg(
+                        Arg::new("public_key")
+                            .long("public-key")
+                            .value_name("KEY")
+                            .help(t!("help.hosts_subscribe_public_key").to_string()),
+                    )
+                    .arg(dry_run_arg()),
             )
             .subcommand(
                 Command::new("unsubscribe")
@@ -35,17 +79,371 @@ pub fn register_command(app: &mut Command) {
                             .help(t!("help.hosts_url").to_string())
                             .required(true)
                             .index(1),
+                    )
+                    .arg(dry_run_arg()),
+            )
+            .subcommand(
+                Command::new("disable")
+                    .about(t!("command.hosts.disable.description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("for")
+                            .long("for")
+                            .value_name("DURATION")
+                            .help(t!("help.hosts_disable_for").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("enable")
+                    .about(t!("command.hosts.enable.description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
                     ),
             )
             .subcommand(
                 Command::new("list").about(t!("command.hosts.list.description").to_string()),
             )
             .subcommand(
-                Command::new("update").about(t!("command.hosts.update.description").to_string()),
+                Command::new("add")
+                    .about(t!("command.hosts.add.description").to_string())
+                    .arg(
+                        Arg::new("ip")
+                            .help(t!("help.hosts_ip").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("names")
+                            .help(t!("help.hosts_names").to_string())
+                            .required(true)
+                            .num_args(1..)
+                            .index(2),
+                    )
+                    .arg(
+                        Arg::new("comment")
+                            .long("comment")
+                            .value_name("TEXT")
+                            .help(t!("help.hosts_comment").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("remove")
+                    .about(t!("command.hosts.remove.description").to_string())
+                    .arg(
+                        Arg::new("name")
+                            .help(t!("help.hosts_name").to_string())
+                            .required(true)
+                            .index(1),
+                    ),
+            )
+            .subcommand(
+                Command::new("entries").about(t!("command.hosts.entries.description").to_string()),
+            )
+            .subcommand(
+                Command::new("search")
+                    .about(t!("command.hosts.search.description").to_string())
+                    .arg(
+                        Arg::new("pattern")
+                            .help(t!("help.hosts_search_pattern").to_string())
+                            .required(true)
+                            .index(1),
+                    ),
+            )
+            .subcommand(
+                Command::new("filter")
+                    .about(t!("command.hosts.filter.description").to_string())
+                    .subcommand(
+                        Command::new("add")
+                            .about(t!("command.hosts.filter.add_description").to_string())
+                            .arg(
+                                Arg::new("target")
+                                    .help(t!("help.hosts_target").to_string())
+                                    .required(true)
+                                    .index(1),
+                            )
+                            .arg(
+                                Arg::new("include")
+                                    .long("include")
+                                    .value_name("PATTERN")
+                                    .action(clap::ArgAction::Append)
+                                    .help(t!("help.hosts_filter_include").to_string()),
+                            )
+                            .arg(
+                                Arg::new("exclude")
+                                    .long("exclude")
+                                    .value_name("PATTERN")
+                                    .action(clap::ArgAction::Append)
+                                    .help(t!("help.hosts_filter_exclude").to_string()),
+                            ),
+                    )
+                    .subcommand(
+                        Command::new("remove")
+                            .about(t!("command.hosts.filter.remove_description").to_string())
+                            .arg(
+                                Arg::new("target")
+                                    .help(t!("help.hosts_target").to_string())
+                                    .required(true)
+                                    .index(1),
+                            )
+                            .arg(
+                                Arg::new("pattern")
+                                    .help(t!("help.hosts_filter_pattern").to_string())
+                                    .required(true)
+                                    .index(2),
+                            ),
+                    )
+                    .subcommand(
+                        Command::new("list")
+                            .about(t!("command.hosts.filter.list_description").to_string())
+                            .arg(
+                                Arg::new("target")
+                                    .help(t!("help.hosts_target").to_string())
+                                    .required(false)
+                                    .index(1),
+                            ),
+                    ),
+            )
+            .subcommand(
+                Command::new("rewrite")
+                    .about(t!("command.hosts.rewrite.description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("ip")
+                            .help(t!("help.hosts_rewrite_ip").to_string())
+                            .required(false)
+                            .index(2),
+                    )
+                    .arg(
+                        Arg::new("ipv6_twin")
+                            .long("ipv6-twin")
+                            .value_name("MODE")
+                            .value_parser(["on", "off", "default"])
+                            .help(t!("help.hosts_ipv6_twin").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("label")
+                    .about(t!("command.hosts.label.description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("name")
+                            .long("name")
+                            .value_name("NAME")
+                            .help(t!("help.hosts_label_name").to_string()),
+                    )
+                    .arg(
+                        Arg::new("notes")
+                            .long("notes")
+                            .value_name("TEXT")
+                            .help(t!("help.hosts_label_notes").to_string()),
+                    )
+                    .arg(tag_arg(t!("help.hosts_tag").to_string()))
+                    .arg(
+                        Arg::new("untag")
+                            .long("untag")
+                            .value_name("TAG")
+                            .action(clap::ArgAction::Append)
+                            .help(t!("help.hosts_untag").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("pin")
+                    .about(t!("command.hosts.integrity.pin_description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("sha256")
+                            .help(t!("help.hosts_pin_sha256").to_string())
+                            .index(2),
+                    )
+                    .arg(
+                        Arg::new("clear")
+                            .long("clear")
+                            .action(clap::ArgAction::SetTrue)
+                            .conflicts_with("sha256")
+                            .help(t!("help.hosts_pin_clear").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("pubkey")
+                    .about(t!("command.hosts.integrity.pubkey_description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("key")
+                            .help(t!("help.hosts_pubkey_key").to_string())
+                            .index(2),
+                    )
+                    .arg(
+                        Arg::new("signature_url")
+                            .long("signature-url")
+                            .value_name("URL")
+                            .help(t!("help.hosts_signature_url").to_string()),
+                    )
+                    .arg(
+                        Arg::new("clear")
+                            .long("clear")
+                            .action(clap::ArgAction::SetTrue)
+                            .help(t!("help.hosts_pubkey_clear").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("conflicts")
+                    .about(t!("command.hosts.conflicts.description").to_string()),
+            )
+            .subcommand(
+                Command::new("export")
+                    .about(t!("command.hosts.export.description").to_string())
+                    .arg(
+                        Arg::new("format")
+                            .long("format")
+                            .value_name("FORMAT")
+                            .value_parser(ExportFormat::NAMES)
+                            .default_value("hosts")
+                            .help(t!("help.hosts_export_format").to_string()),
+                    )
+                    .arg(
+                        Arg::new("output")
+                            .long("output")
+                            .short('o')
+                            .value_name("FILE")
+                            .help(t!("help.hosts_export_output").to_string()),
+                    )
+                    .arg(
+                        Arg::new("apply")
+                            .long("apply")
+                            .action(clap::ArgAction::SetTrue)
+                            .requires("output")
+                            .help(t!("help.hosts_export_apply").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("update")
+                    .about(t!("command.hosts.update.description").to_string())
+                    .arg(
+                        Arg::new("targets")
+                            .help(t!("help.hosts_update_targets").to_string())
+                            .num_args(0..)
+                            .index(1),
+                    )
+                    .arg(tag_arg(t!("help.hosts_update_tag").to_string()))
+                    .arg(dry_run_arg())
+                    .arg(
+                        Arg::new("offline")
+                            .long("offline")
+                            .action(clap::ArgAction::SetTrue)
+                            .help(t!("help.hosts_offline").to_string()),
+                    )
+                    .arg(
+                        Arg::new("accept_new")
+                            .long("accept-new")
+                            .action(clap::ArgAction::SetTrue)
+                            .help(t!("help.hosts_accept_new").to_string()),
+                    ),
+            )
+            .subcommand(
+                Command::new("priority")
+                    .about(t!("command.hosts.priority.description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("position")
+                            .help(t!("help.hosts_position").to_string())
+                            .required(true)
+                            .value_parser(clap::value_parser!(usize))
+                            .index(2),
+                    ),
+            )
+            .subcommand(
+                Command::new("move")
+                    .about(t!("command.hosts.move.description").to_string())
+                    .arg(
+                        Arg::new("target")
+                            .help(t!("help.hosts_target").to_string())
+                            .required(true)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("direction")
+                            .help(t!("help.hosts_direction").to_string())
+                            .required(true)
+                            .value_parser(["up", "down", "top", "bottom"])
+                            .index(2),
+                    ),
+            )
+            .subcommand(
+                Command::new("cache")
+                    .about(t!("command.hosts.cache.description").to_string())
+                    .subcommand(
+                        Command::new("ls")
+                            .about(t!("command.hosts.cache.ls_description").to_string()),
+                    )
+                    .subcommand(
+                        Command::new("clear")
+                            .about(t!("command.hosts.cache.clear_description").to_string())
+                            .arg(
+                                Arg::new("target")
+                                    .help(t!("help.hosts_cache_target").to_string())
+                                    .required(false)
+                                    .index(1),
+                            ),
+                    ),
             )
             .subcommand(
                 Command::new("backup").about(t!("command.hosts.backup.description").to_string()),
             )
+            .subcommand(
+                Command::new("diff")
+                    .about(t!("command.hosts.diff.description").to_string())
+                    .arg(
+                        Arg::new("backup")
+                            .help(t!("help.hosts_diff_backup").to_string())
+                            .required(false)
+                            .index(1),
+                    )
+                    .arg(
+                        Arg::new("backup2")
+                            .help(t!("help.hosts_diff_backup2").to_string())
+                            .required(false)
+                            .index(2),
+                    )
+                    .arg(
+                        Arg::new("entries")
+                            .long("entries")
+                            .action(clap::ArgAction::SetTrue)
+                            .help(t!("help.hosts_diff_entries").to_string()),
+                    ),
+            )
             .subcommand(
                 Command::new("restore")
                     .about(t!("command.hosts.restore.description").to_string())
@@ -59,24 +457,237 @@ pub fn register_command(app: &mut Command) {
     );
 }
 
+/// `--dry-run` 参数：只显示 hosts 文件将发生的变化，不写入
+fn dry_run_arg() -> Arg {
+    Arg::new("dry_run")
+        .long("dry-run")
+        .action(clap::ArgAction::SetTrue)
+        .help(t!("help.hosts_dry_run").to_string())
+}
+
+/// 可重复的 `--tag` 参数
+fn tag_arg(help: String) -> Arg {
+    Arg::new("tag")
+        .long("tag")
+        .value_name("TAG")
+        .action(clap::ArgAction::Append)
+        .help(help)
+}
+
+/// 获取可重复参数的所有值，未指定时返回空列表
+fn values(matches: &ArgMatches, name: &str) -> Vec<String> {
+    matches
+        .get_many::<String>(name)
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default()
+}
+
 /// 执行 hosts 命令
 pub fn execute(matches: &ArgMatches) -> Result<()> {
+    // 命令行指定的目标文件优先级最高
+    if let Some(path) = matches.get_one::<String>("hosts_file") {
+        set_hosts_file_override(path);
+    }
+
+    let hosts_path = get_hosts_file_path()?;
+    if !is_default_hosts_file(&hosts_path) {
+        let message = t!("command.hosts.target_file", path = hosts_path.display());
+        // 导出到标准输出时提示信息不能混入导出内容
+        match matches.subcommand() {
+            Some(("export", sub_matches)) if !sub_matches.contains_id("output") => {
+                eprintln!("{message}")
+            }
+            _ => println!("{message}"),
+        }
+    }
+
     match matches.subcommand() {
         Some(("subscribe", sub_matches)) => {
             let url = sub_matches
                 .get_one::<String>("url")
                 .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
-            handle_subscribe(url)
+            let name = sub_matches.get_one::<String>("name").map(|s| s.as_str());
+            let format = sub_matches
+                .get_one::<String>("format")
+                .map(|s| s.parse::<ListFormat>())
+                .transpose()?;
+            let sha256 = sub_matches.get_one::<String>("sha256").map(|s| s.as_str());
+            let public_key = sub_matches
+                .get_one::<String>("public_key")
+                .map(|s| s.as_str());
+            handle_subscribe(
+                url,
+                name,
+                &values(sub_matches, "tag"),
+                format,
+                sha256,
+                public_key,
+                sub_matches.get_flag("dry_run"),
+            )
         }
         Some(("unsubscribe", sub_matches)) => {
             let url = sub_matches
                 .get_one::<String>("url")
                 .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
-            handle_unsubscribe(url)
+            handle_unsubscribe(url, sub_matches.get_flag("dry_run"))
+        }
+        Some(("disable", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let duration = sub_matches.get_one::<String>("for").map(|s| s.as_str());
+            handle_disable(target, duration)
+        }
+        Some(("enable", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            handle_enable(target)
         }
         Some(("list", _)) => handle_list(),
-        Some(("update", _)) => handle_update(),
+        Some(("add", sub_matches)) => {
+            let ip = sub_matches
+                .get_one::<String>("ip")
+                .map(|s| s.as_str())
+                .unwrap_or_default();
+            let names: Vec<String> = sub_matches
+                .get_many::<String>("names")
+                .map(|values| values.cloned().collect())
+                .unwrap_or_default();
+            let comment = sub_matches.get_one::<String>("comment").map(|s| s.as_str());
+            handle_add(ip, &names, comment)
+        }
+        Some(("remove", sub_matches)) => {
+            let name = sub_matches
+                .get_one::<String>("name")
+                .map(|s| s.as_str())
+                .unwrap_or_default();
+            handle_remove(name)
+        }
+        Some(("entries", _)) => handle_entries(),
+        Some(("search", sub_matches)) => {
+            let pattern = sub_matches
+                .get_one::<String>("pattern")
+                .map(|s| s.as_str())
+                .unwrap_or_default();
+            handle_search(pattern)
+        }
+        Some(("filter", filter_matches)) => match filter_matches.subcommand() {
+            Some(("add", sub_matches)) => {
+                let target = sub_matches
+                    .get_one::<String>("target")
+                    .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+                handle_filter_add(
+                    target,
+                    &values(sub_matches, "include"),
+                    &values(sub_matches, "exclude"),
+                )
+            }
+            Some(("remove", sub_matches)) => {
+                let target = sub_matches
+                    .get_one::<String>("target")
+                    .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+                let pattern = sub_matches
+                    .get_one::<String>("pattern")
+                    .map(|s| s.as_str())
+                    .unwrap_or_default();
+                handle_filter_remove(target, pattern)
+            }
+            Some(("list", sub_matches)) => {
+                handle_filter_list(sub_matches.get_one::<String>("target").map(|s| s.as_str()))
+            }
+            _ => handle_filter_list(None),
+        },
+        Some(("rewrite", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let ip = sub_matches.get_one::<String>("ip").map(|s| s.as_str());
+            let ipv6_twin = sub_matches
+                .get_one::<String>("ipv6_twin")
+                .map(|s| s.as_str());
+            handle_rewrite(target, ip, ipv6_twin)
+        }
+        Some(("label", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let name = sub_matches.get_one::<String>("name").map(|s| s.as_str());
+            let notes = sub_matches.get_one::<String>("notes").map(|s| s.as_str());
+            handle_label(
+                target,
+                name,
+                notes,
+                &values(sub_matches, "tag"),
+                &values(sub_matches, "untag"),
+            )
+        }
+        Some(("pin", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let sha256 = sub_matches.get_one::<String>("sha256").map(|s| s.as_str());
+            handle_pin(target, sha256, sub_matches.get_flag("clear"))
+        }
+        Some(("pubkey", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let key = sub_matches.get_one::<String>("key").map(|s| s.as_str());
+            let signature_url = sub_matches
+                .get_one::<String>("signature_url")
+                .map(|s| s.as_str());
+            handle_public_key(target, key, signature_url, sub_matches.get_flag("clear"))
+        }
+        Some(("conflicts", _)) => handle_conflicts(),
+        Some(("export", sub_matches)) => {
+            let format = sub_matches
+                .get_one::<String>("format")
+                .map(|s| s.parse::<ExportFormat>())
+                .transpose()?
+                .unwrap_or(ExportFormat::Hosts);
+            let output = sub_matches.get_one::<String>("output").map(|s| s.as_str());
+            handle_export(format, output, sub_matches.get_flag("apply"))
+        }
+        Some(("update", sub_matches)) => handle_update(
+            &values(sub_matches, "targets"),
+            &values(sub_matches, "tag"),
+            sub_matches.get_flag("dry_run"),
+            sub_matches.get_flag("offline"),
+            sub_matches.get_flag("accept_new"),
+        ),
+        Some(("cache", cache_matches)) => match cache_matches.subcommand() {
+            Some(("clear", sub_matches)) => {
+                handle_cache_clear(sub_matches.get_one::<String>("target").map(|s| s.as_str()))
+            }
+            _ => handle_cache_list(),
+        },
+        Some(("priority", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let position = sub_matches
+                .get_one::<usize>("position")
+                .copied()
+                .unwrap_or(1);
+            handle_priority(target, position)
+        }
+        Some(("move", sub_matches)) => {
+            let target = sub_matches
+                .get_one::<String>("target")
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.missing_url")))?;
+            let direction = sub_matches
+                .get_one::<String>("direction")
+                .map(|s| s.as_str())
+                .unwrap_or("up");
+            handle_move(target, direction)
+        }
         Some(("backup", _)) => handle_backup(),
+        Some(("diff", sub_matches)) => handle_diff(
+            sub_matches.get_one::<String>("backup").map(|s| s.as_str()),
+            sub_matches.get_one::<String>("backup2").map(|s| s.as_str()),
+            sub_matches.get_flag("entries"),
+        ),
         Some(("restore", sub_matches)) => {
             let backup_file = sub_matches.get_one::<String>("backup_file");
             handle_restore(backup_file.map(|s| s.as_str()))
@@ -89,123 +700,454 @@ pub fn execute(matches: &ArgMatches) -> Result<()> {
 }
 
 /// Hosts 文件结构
+///
+/// 按行保存文件内容，未修改时可以逐字节还原；
+/// 编辑只会改动受影响的行或订阅块，其余内容保持原样和原有位置。
 #[derive(Debug, Clone)]
 pub struct HostsFileStructure {
-    pub other_content: Vec<String>,
-    pub subscription_blocks: HashMap<String, Vec<String>>,
+    pub lines: Vec<HostsLine>,
+    /// 文件是否以换行符结尾
+    pub trailing_newline: bool,
+}
+
+/// hosts 文件中的一行，或一个完整的订阅块
+#[derive(Debug, Clone)]
+pub enum HostsLine {
+    /// hosts 条目，无法解析的行 `entry` 为 None
+    Entry {
+        raw: String,
+        entry: Option<HostEntry>,
+    },
+    /// 注释行（原始行）
+    Comment(String),
+    /// 空行（保留原始空白字符）
+    Blank(String),
+    /// xdev 管理的块，位于其在文件中的原始位置
+    ManagedBlock(ManagedBlock),
+}
+
+/// xdev 管理的块的类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockKind {
+    /// 订阅块，记录订阅 URL
+    Subscription(String),
+    /// 本地条目块
+    Local,
+}
+
+/// xdev 管理的块（包含开始和结束标记行）
+#[derive(Debug, Clone)]
+pub struct ManagedBlock {
+    pub kind: BlockKind,
+    pub lines: Vec<String>,
+}
+
+impl ManagedBlock {
+    /// 订阅块的 URL，本地条目块返回 None
+    pub fn subscription_url(&self) -> Option<&str> {
+        match &self.kind {
+            BlockKind::Subscription(url) => Some(url),
+            BlockKind::Local => None,
+        }
+    }
+
+    /// 是否为指定 URL 的订阅块
+    fn is_subscription(&self, url: &str) -> bool {
+        self.subscription_url() == Some(url)
+    }
+
+    /// 注释掉或恢复块内的所有条目，标记行和其他注释保持不变
+    pub fn set_enabled(&mut self, enabled: bool) {
+        for line in &mut self.lines {
+            if enabled {
+                if let Some(original) = line.strip_prefix(HOSTS_DISABLED_LINE_PREFIX) {
+                    *line = original.to_string();
+                }
+            } else if HostEntry::parse(line, EntrySource::Unmanaged).is_some() {
+                *line = format!("{HOSTS_DISABLED_LINE_PREFIX}{line}");
+            }
+        }
+    }
+
+    /// 解析块内的所有有效条目
+    pub fn entries(&self) -> Vec<HostEntry> {
+        let source = match &self.kind {
+            BlockKind::Subscription(url) => EntrySource::Subscription(url.clone()),
+            BlockKind::Local => EntrySource::Local,
+        };
+        self.lines
+            .iter()
+            .filter_map(|line| HostEntry::parse(line, source.clone()))
+            .collect()
+    }
+}
+
+impl HostsLine {
+    /// 将普通行解析为对应的类型
+    fn from_raw(line: &str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            HostsLine::Blank(line.to_string())
+        } else if trimmed.starts_with('#') {
+            HostsLine::Comment(line.to_string())
+        } else {
+            HostsLine::Entry {
+                raw: line.to_string(),
+                entry: HostEntry::parse(line, EntrySource::Unmanaged),
+            }
+        }
+    }
+
+    /// 追加该行对应的原始文本
+    fn write_raw<'a>(&'a self, output: &mut Vec<&'a str>) {
+        match self {
+            HostsLine::Entry { raw, .. } | HostsLine::Comment(raw) | HostsLine::Blank(raw) => {
+                output.push(raw)
+            }
+            HostsLine::ManagedBlock(block) => {
+                output.extend(block.lines.iter().map(|line| line.as_str()))
+            }
+        }
+    }
 }
 
 impl FileStructure for HostsFileStructure {
     fn parse(content: &str) -> Self {
         let mut structure = HostsFileStructure::new();
-        let mut current_block: Option<String> = None;
-        let mut current_block_content = Vec::new();
-
-        for line in content.lines() {
-            let line = line.to_string();
-
-            // 检查是否是订阅块的开始
-            if let Some(url) = extract_subscription_url_from_start_marker(&line) {
-                // 如果之前有未结束的块，保存到 other_content
-                if let Some(block_url) = current_block.take() {
-                    structure
-                        .subscription_blocks
-                        .insert(block_url, current_block_content);
-                    current_block_content = Vec::new();
+        structure.trailing_newline = content.ends_with('\n');
+
+        let body = content.strip_suffix('\n').unwrap_or(content);
+        if content.is_empty() {
+            return structure;
+        }
+
+        let mut current_block: Option<ManagedBlock> = None;
+
+        // 按 '\n' 切分以保留 '\r' 等原始字符
+        for line in body.split('\n') {
+            // 检查是否是托管块的开始
+            if let Some(kind) = extract_block_kind_from_start_marker(line) {
+                // 如果之前有未结束的块，按原样保留
+                if let Some(block) = current_block.take() {
+                    structure.lines.push(HostsLine::ManagedBlock(block));
                 }
-                current_block = Some(url);
-                current_block_content.push(line);
+                current_block = Some(ManagedBlock {
+                    kind,
+                    lines: vec![line.to_string()],
+                });
                 continue;
             }
 
-            // 检查是否是订阅块的结束
-            if is_subscription_end_marker(&line) {
-                if let Some(block_url) = current_block.take() {
-                    current_block_content.push(line);
-                    structure
-                        .subscription_blocks
-                        .insert(block_url, current_block_content);
-                    current_block_content = Vec::new();
+            // 检查是否是托管块的结束
+            if is_block_end_marker(line) {
+                if let Some(mut block) = current_block.take() {
+                    block.lines.push(line.to_string());
+                    structure.lines.push(HostsLine::ManagedBlock(block));
                 } else {
                     // 没有匹配的开始标记，当作普通内容
-                    structure.other_content.push(line);
+                    structure.lines.push(HostsLine::from_raw(line));
                 }
                 continue;
             }
 
             // 处理普通行
-            if current_block.is_some() {
-                current_block_content.push(line);
-            } else {
-                structure.other_content.push(line);
+            match current_block.as_mut() {
+                Some(block) => block.lines.push(line.to_string()),
+                None => structure.lines.push(HostsLine::from_raw(line)),
             }
         }
 
         // 处理未结束的块
-        if let Some(block_url) = current_block {
-            structure
-                .subscription_blocks
-                .insert(block_url, current_block_content);
+        if let Some(block) = current_block {
+            structure.lines.push(HostsLine::ManagedBlock(block));
         }
 
         structure
     }
 
     fn reconstruct(&self) -> String {
-        // 预分配容量以提高性能
-        let estimated_size = self.other_content.len()
-            + self
-                .subscription_blocks
-                .values()
-                .map(|v| v.len())
-                .sum::<usize>()
-            + self.subscription_blocks.len(); // 为分隔符预留空间
-        let mut content = Vec::with_capacity(estimated_size);
-
-        // 添加其他内容
-        content.extend(self.other_content.iter().cloned());
-
-        // 添加所有订阅块
-        for block_lines in self.subscription_blocks.values() {
-            if !content.is_empty() && content.last().is_some_and(|last| !last.is_empty()) {
-                content.push(String::new()); // 添加空行分隔
-            }
-            content.extend(block_lines.iter().cloned());
+        let mut output = Vec::with_capacity(self.lines.len());
+        for line in &self.lines {
+            line.write_raw(&mut output);
         }
 
-        content.join("\n")
+        let mut content = output.join("\n");
+        if self.trailing_newline && !output.is_empty() {
+            content.push('\n');
+        }
+        content
     }
 }
 
 impl HostsFileStructure {
     pub fn new() -> Self {
         Self {
-            other_content: Vec::new(),
-            subscription_blocks: HashMap::new(),
+            lines: Vec::new(),
+            trailing_newline: true,
+        }
+    }
+
+    /// 遍历所有订阅块（按文件中的顺序）
+    pub fn subscription_blocks(&self) -> impl Iterator<Item = &ManagedBlock> {
+        self.lines.iter().filter_map(|line| match line {
+            HostsLine::ManagedBlock(block) if block.subscription_url().is_some() => Some(block),
+            _ => None,
+        })
+    }
+
+    /// 按文件顺序获取所有有效条目（包括未管理内容和订阅块）
+    pub fn entries(&self) -> Vec<HostEntry> {
+        let mut entries = Vec::new();
+        for line in &self.lines {
+            match line {
+                HostsLine::Entry {
+                    entry: Some(entry), ..
+                } => entries.push(entry.clone()),
+                HostsLine::ManagedBlock(block) => entries.extend(block.entries()),
+                _ => {}
+            }
+        }
+        entries
+    }
+
+    /// 查找主机名包含指定关键字的条目（不区分大小写）
+    pub fn find_entries(&self, pattern: &str) -> Vec<HostEntry> {
+        let pattern = pattern.to_lowercase();
+        self.entries()
+            .into_iter()
+            .filter(|entry| {
+                entry
+                    .hostnames()
+                    .any(|name| name.to_lowercase().contains(&pattern))
+            })
+            .collect()
+    }
+
+    /// 查找订阅块所在的位置
+    fn find_block(&self, url: &str) -> Option<usize> {
+        self.lines.iter().position(
+            |line| matches!(line, HostsLine::ManagedBlock(block) if block.is_subscription(url)),
+        )
+    }
+
+    /// hosts 文件中该订阅块当前的条目，订阅块不存在时返回 None
+    pub fn subscription_entries(&self, url: &str) -> Option<Vec<HostEntry>> {
+        match &self.lines[self.find_block(url)?] {
+            HostsLine::ManagedBlock(block) => Some(block.entries()),
+            _ => None,
         }
     }
 
     /// 添加或更新订阅块
-    pub fn add_or_update_subscription(&mut self, url: &str, content: &str) {
-        let block_content = create_subscription_block(url, content);
-        let block_lines: Vec<String> = block_content.lines().map(|s| s.to_string()).collect();
-        self.subscription_blocks
-            .insert(url.to_string(), block_lines);
+    ///
+    /// 已存在的块原位替换；新块追加到文件末尾，并用一个空行与前面的内容分隔。
+    pub fn add_or_update_subscription(&mut self, url: &str, entries: &[HostEntry]) {
+        let block_content = create_subscription_block(url, entries);
+        let block = ManagedBlock {
+            kind: BlockKind::Subscription(url.to_string()),
+            lines: block_content.lines().map(|s| s.to_string()).collect(),
+        };
+
+        match self.find_block(url) {
+            Some(index) => {
+                self.lines[index] = HostsLine::ManagedBlock(block);
+                // 移除同一订阅的重复块
+                let mut seen = false;
+                self.lines.retain(|line| match line {
+                    HostsLine::ManagedBlock(block) if block.is_subscription(url) => {
+                        !std::mem::replace(&mut seen, true)
+                    }
+                    _ => true,
+                });
+            }
+            None => self.append_block(block),
+        }
     }
 
-    /// 删除订阅块
+    /// 将新块追加到文件末尾，并用一个空行与前面的内容分隔
+    fn append_block(&mut self, block: ManagedBlock) {
+        if self
+            .lines
+            .last()
+            .is_some_and(|last| !matches!(last, HostsLine::Blank(_)))
+        {
+            self.lines.push(HostsLine::Blank(String::new())); // 添加空行分隔
+        }
+        self.lines.push(HostsLine::ManagedBlock(block));
+        self.trailing_newline = true;
+    }
+
+    /// 启用或禁用订阅块，未找到订阅块时返回 false
+    pub fn set_subscription_enabled(&mut self, url: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for line in &mut self.lines {
+            if let HostsLine::ManagedBlock(block) = line
+                && block.is_subscription(url)
+            {
+                block.set_enabled(enabled);
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// 删除订阅块，并移除其前面多余的空行分隔
     pub fn remove_subscription(&mut self, url: &str) -> bool {
-        self.subscription_blocks.remove(url).is_some()
+        let mut removed = false;
+
+        while let Some(index) = self.find_block(url) {
+            self.remove_block_at(index);
+            removed = true;
+        }
+
+        removed
+    }
+
+    /// 删除指定位置的块，并移除其前面多余的空行分隔
+    fn remove_block_at(&mut self, index: usize) {
+        self.lines.remove(index);
+
+        let next_is_blank_or_end = self
+            .lines
+            .get(index)
+            .is_none_or(|line| matches!(line, HostsLine::Blank(_)));
+        if index > 0 && matches!(self.lines[index - 1], HostsLine::Blank(_)) && next_is_blank_or_end
+        {
+            self.lines.remove(index - 1);
+        }
+    }
+
+    /// 获取本地条目块中的所有条目
+    pub fn local_entries(&self) -> Vec<HostEntry> {
+        self.find_local_block()
+            .and_then(|index| match &self.lines[index] {
+                HostsLine::ManagedBlock(block) => Some(block.entries()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// 添加本地条目，相同主机名的已有本地映射会被替换，返回被替换的主机名数量
+    pub fn add_local_entry(&mut self, entry: HostEntry) -> usize {
+        let mut entries = self.local_entries();
+        let mut replaced = 0;
+        for hostname in entry.hostnames() {
+            replaced += remove_hostname(&mut entries, hostname);
+        }
+        entries.push(entry);
+        self.set_local_entries(entries);
+        replaced
+    }
+
+    /// 从本地条目中移除主机名，返回是否找到
+    pub fn remove_local_hostname(&mut self, hostname: &str) -> bool {
+        let mut entries = self.local_entries();
+        if remove_hostname(&mut entries, hostname) == 0 {
+            return false;
+        }
+        self.set_local_entries(entries);
+        true
     }
 
-    /// 获取所有订阅的 URL
+    /// 查找本地条目块所在的位置
+    fn find_local_block(&self) -> Option<usize> {
+        self.lines.iter().position(
+            |line| matches!(line, HostsLine::ManagedBlock(block) if block.kind == BlockKind::Local),
+        )
+    }
+
+    /// 用给定的条目重写本地条目块，没有条目时删除该块
+    ///
+    /// 新建的本地条目块放在第一个订阅块之前，使本地映射优先于订阅生效。
+    fn set_local_entries(&mut self, entries: Vec<HostEntry>) {
+        let existing = self.find_local_block();
+
+        if entries.is_empty() {
+            if let Some(index) = existing {
+                self.remove_block_at(index);
+            }
+            return;
+        }
+
+        let block = create_local_block(&entries);
+        match existing {
+            Some(index) => self.lines[index] = HostsLine::ManagedBlock(block),
+            None => match self.find_first_subscription_block() {
+                Some(index) => {
+                    self.lines.insert(index, HostsLine::ManagedBlock(block));
+                    self.lines
+                        .insert(index + 1, HostsLine::Blank(String::new()));
+                }
+                None => self.append_block(block),
+            },
+        }
+    }
+
+    /// 查找第一个订阅块所在的位置
+    fn find_first_subscription_block(&self) -> Option<usize> {
+        self.lines.iter().position(
+            |line| matches!(line, HostsLine::ManagedBlock(block) if block.subscription_url().is_some()),
+        )
+    }
+
+    /// 获取所有订阅的 URL（按文件中的顺序）
     pub fn get_all_subscriptions(&self) -> Vec<String> {
-        self.subscription_blocks.keys().cloned().collect()
+        self.subscription_blocks()
+            .filter_map(|block| block.subscription_url())
+            .map(|url| url.to_string())
+            .collect()
     }
+
+    /// 按给定的订阅顺序排列订阅块，不在列表中的块保持原有相对顺序并排在最后
+    ///
+    /// 只在订阅块原本占据的位置之间调整，不移动其他内容。
+    pub fn sort_subscriptions(&mut self, order: &[String]) {
+        let slots: Vec<usize> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                matches!(line, HostsLine::ManagedBlock(block) if block.subscription_url().is_some())
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut blocks: Vec<HostsLine> = slots
+            .iter()
+            .map(|&index| {
+                std::mem::replace(&mut self.lines[index], HostsLine::Blank(String::new()))
+            })
+            .collect();
+        blocks.sort_by_key(|line| match line {
+            HostsLine::ManagedBlock(block) => order
+                .iter()
+                .position(|url| block.is_subscription(url))
+                .unwrap_or(order.len()),
+            _ => order.len(),
+        });
+
+        for (index, block) in slots.into_iter().zip(blocks) {
+            self.lines[index] = block;
+        }
+    }
+}
+
+/// 从开始标记中识别托管块的类型
+fn extract_block_kind_from_start_marker(line: &str) -> Option<BlockKind> {
+    if line.trim_end() == HOSTS_LOCAL_START_MARKER {
+        return Some(BlockKind::Local);
+    }
+    extract_subscription_url_from_start_marker(line).map(BlockKind::Subscription)
+}
+
+/// 检查是否是托管块的结束标记
+fn is_block_end_marker(line: &str) -> bool {
+    line.trim_end() == HOSTS_LOCAL_END_MARKER || is_subscription_end_marker(line)
 }
 
 /// 从开始标记中提取订阅 URL
 fn extract_subscription_url_from_start_marker(line: &str) -> Option<String> {
+    let line = line.trim_end();
     if line.starts_with(HOSTS_SUBSCRIPTION_START_MARKER)
         && line.ends_with(HOSTS_SUBSCRIPTION_MARKER_SUFFIX)
     {
@@ -219,12 +1161,45 @@ fn extract_subscription_url_from_start_marker(line: &str) -> Option<String> {
 
 /// 检查是否是订阅结束标记
 fn is_subscription_end_marker(line: &str) -> bool {
+    let line = line.trim_end();
     line.starts_with(HOSTS_SUBSCRIPTION_END_MARKER)
         && line.ends_with(HOSTS_SUBSCRIPTION_MARKER_SUFFIX)
 }
 
+/// 从条目列表中移除主机名，没有剩余主机名的条目整体删除，返回移除的数量
+fn remove_hostname(entries: &mut Vec<HostEntry>, hostname: &str) -> usize {
+    let mut removed = 0;
+    entries.retain_mut(|entry| {
+        let mut names: Vec<String> = entry.hostnames().map(|name| name.to_string()).collect();
+        let before = names.len();
+        names.retain(|name| !name.eq_ignore_ascii_case(hostname));
+        removed += before - names.len();
+
+        if names.is_empty() {
+            return false;
+        }
+        entry.canonical = names.remove(0);
+        entry.aliases = names;
+        true
+    });
+    removed
+}
+
+/// 创建本地条目块
+fn create_local_block(entries: &[HostEntry]) -> ManagedBlock {
+    let mut lines = Vec::with_capacity(entries.len() + 2);
+    lines.push(HOSTS_LOCAL_START_MARKER.to_string());
+    lines.extend(entries.iter().map(|entry| entry.to_line()));
+    lines.push(HOSTS_LOCAL_END_MARKER.to_string());
+
+    ManagedBlock {
+        kind: BlockKind::Local,
+        lines,
+    }
+}
+
 /// 创建订阅块内容
-fn create_subscription_block(url: &str, hosts_content: &str) -> String {
+fn create_subscription_block(url: &str, entries: &[HostEntry]) -> String {
     let mut block = Vec::new();
 
     // 开始标记
@@ -237,12 +1212,9 @@ fn create_subscription_block(url: &str, hosts_content: &str) -> String {
     block.push(format!("# 订阅时间: {timestamp} (UTC timestamp)"));
     block.push(String::new());
 
-    // 添加 hosts 内容（过滤掉空行和注释）
-    for line in hosts_content.lines() {
-        let line = line.trim();
-        if !line.is_empty() && !line.starts_with('#') {
-            block.push(line.to_string());
-        }
+    // 添加 hosts 条目（统一格式）
+    for entry in entries {
+        block.push(entry.to_line());
     }
 
     block.push(String::new());
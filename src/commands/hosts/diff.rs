@@ -0,0 +1,186 @@
+use crate::commands::hosts::{
+    backup::{list_hosts_backups, resolve_backup},
+    core::HostsFileStructure,
+    create_hosts_manager,
+};
+use crate::core::diff::unified_diff;
+use crate::core::filesystem::FileStructure;
+use crate::core::i18n::t;
+use crate::core::table::{add_table_row, create_subscription_table, print_table, set_table_header};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::Path;
+
+/// 两个 hosts 文件之间单个主机名的变化
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum EntryChange {
+    Added(IpAddr),
+    Removed(IpAddr),
+    Changed(IpAddr, IpAddr),
+}
+
+/// 处理差异命令
+///
+/// 未指定备份时对比最新备份和当前文件；指定一个备份时对比该备份和当前文件；
+/// 指定两个备份时对比这两个备份。`entries` 为 true 时按主机名输出增删改。
+pub fn handle_diff(first: Option<&str>, second: Option<&str>, entries: bool) -> Result<()> {
+    let hosts_manager = create_hosts_manager()?;
+
+    let old_path = match first {
+        Some(selector) => resolve_backup(&hosts_manager, selector)?,
+        None => list_hosts_backups(&hosts_manager)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("{}", t!("error.hosts_backup_not_found")))?,
+    };
+    let old_content = read_backup(&old_path)?;
+    let old_label = display_name(&old_path);
+
+    let (new_content, new_label) = match second {
+        Some(selector) => {
+            let path = resolve_backup(&hosts_manager, selector)?;
+            (read_backup(&path)?, display_name(&path))
+        }
+        None => {
+            let file_manager = hosts_manager.file_manager();
+            (
+                file_manager.read_file()?,
+                file_manager.file_path.display().to_string(),
+            )
+        }
+    };
+
+    println!(
+        "{}",
+        t!("command.hosts.diff.title", old = old_label, new = new_label)
+    );
+    println!();
+
+    if entries {
+        print_entry_changes(&old_content, &new_content);
+    } else {
+        match unified_diff(&old_content, &new_content, &old_label, &new_label) {
+            Some(diff) => print!("{diff}"),
+            None => println!("{}", t!("command.hosts.diff.identical")),
+        }
+    }
+
+    Ok(())
+}
+
+/// 按主机名输出两个文件之间生效映射的变化
+fn print_entry_changes(old_content: &str, new_content: &str) {
+    let old = effective_mappings(&HostsFileStructure::parse(old_content));
+    let new = effective_mappings(&HostsFileStructure::parse(new_content));
+
+    let mut changes: BTreeMap<&(String, bool), EntryChange> = BTreeMap::new();
+    for (key, old_ip) in &old {
+        match new.get(key) {
+            None => {
+                changes.insert(key, EntryChange::Removed(*old_ip));
+            }
+            Some(new_ip) if new_ip != old_ip => {
+                changes.insert(key, EntryChange::Changed(*old_ip, *new_ip));
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, new_ip) in &new {
+        if !old.contains_key(key) {
+            changes.insert(key, EntryChange::Added(*new_ip));
+        }
+    }
+
+    if changes.is_empty() {
+        println!("{}", t!("command.hosts.diff.no_entry_changes"));
+        return;
+    }
+
+    let mut table = create_subscription_table();
+    set_table_header(
+        &mut table,
+        vec![
+            t!("command.hosts.diff.table_header_change").to_string(),
+            t!("command.hosts.conflicts.table_header_hostname").to_string(),
+            t!("command.hosts.diff.table_header_old_ip").to_string(),
+            t!("command.hosts.diff.table_header_new_ip").to_string(),
+        ],
+    );
+
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for ((hostname, _), change) in &changes {
+        let (kind, old_ip, new_ip) = match change {
+            EntryChange::Added(ip) => {
+                added += 1;
+                (
+                    t!("command.hosts.diff.added"),
+                    String::new(),
+                    ip.to_string(),
+                )
+            }
+            EntryChange::Removed(ip) => {
+                removed += 1;
+                (
+                    t!("command.hosts.diff.removed"),
+                    ip.to_string(),
+                    String::new(),
+                )
+            }
+            EntryChange::Changed(old_ip, new_ip) => {
+                changed += 1;
+                (
+                    t!("command.hosts.diff.changed"),
+                    old_ip.to_string(),
+                    new_ip.to_string(),
+                )
+            }
+        };
+        add_table_row(
+            &mut table,
+            vec![kind.to_string(), hostname.clone(), old_ip, new_ip],
+        );
+    }
+    print_table(&table);
+
+    println!(
+        "{}",
+        t!(
+            "command.hosts.diff.statistics",
+            added = added,
+            removed = removed,
+            changed = changed
+        )
+    );
+}
+
+/// 每个主机名（区分地址族）实际生效的 IP，靠前的条目优先
+fn effective_mappings(structure: &HostsFileStructure) -> BTreeMap<(String, bool), IpAddr> {
+    let mut mappings = BTreeMap::new();
+    for entry in structure.entries() {
+        for hostname in entry.hostnames() {
+            mappings
+                .entry((hostname.to_lowercase(), entry.ip.is_ipv4()))
+                .or_insert(entry.ip);
+        }
+    }
+    mappings
+}
+
+/// 读取备份文件内容
+fn read_backup(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|_| {
+        anyhow::anyhow!(
+            "{}",
+            t!("error.hosts_backup_file_not_exist", path = path.display())
+        )
+    })
+}
+
+/// 备份文件的显示名称
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -0,0 +1,76 @@
+use crate::core::i18n::t;
+use std::fmt;
+use std::net::IpAddr;
+
+/// hosts 条目的来源
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EntrySource {
+    /// 不受 xdev 管理的内容
+    Unmanaged,
+    /// 来自指定 URL 的订阅块
+    Subscription(String),
+    /// 来自本地条目块
+    Local,
+}
+
+impl fmt::Display for EntrySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntrySource::Unmanaged => write!(f, "{}", t!("command.hosts.source.unmanaged")),
+            EntrySource::Subscription(url) => write!(f, "{url}"),
+            EntrySource::Local => write!(f, "{}", t!("command.hosts.source.local")),
+        }
+    }
+}
+
+/// 结构化的 hosts 条目：`<ip> <canonical> [aliases...] [# comment]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostEntry {
+    pub ip: IpAddr,
+    pub canonical: String,
+    pub aliases: Vec<String>,
+    pub comment: Option<String>,
+    pub source: EntrySource,
+}
+
+impl HostEntry {
+    /// 解析单行 hosts 内容，空行、注释行和格式无效的行返回 None
+    pub fn parse(line: &str, source: EntrySource) -> Option<Self> {
+        let (data, comment) = match line.split_once('#') {
+            Some((data, comment)) => (data, Some(comment.trim())),
+            None => (line, None),
+        };
+
+        let mut parts = data.split_whitespace();
+        let ip = parts.next()?.parse::<IpAddr>().ok()?;
+        let canonical = parts.next()?.to_string();
+        let aliases = parts.map(|s| s.to_string()).collect();
+
+        Some(Self {
+            ip,
+            canonical,
+            aliases,
+            comment: comment.filter(|c| !c.is_empty()).map(|c| c.to_string()),
+            source,
+        })
+    }
+
+    /// 该条目映射的所有主机名（规范名在前）
+    pub fn hostnames(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.canonical.as_str()).chain(self.aliases.iter().map(|s| s.as_str()))
+    }
+
+    /// 生成标准格式的 hosts 行
+    pub fn to_line(&self) -> String {
+        let mut line = format!("{} {}", self.ip, self.canonical);
+        for alias in &self.aliases {
+            line.push(' ');
+            line.push_str(alias);
+        }
+        if let Some(comment) = &self.comment {
+            line.push_str(" # ");
+            line.push_str(comment);
+        }
+        line
+    }
+}
@@ -0,0 +1,227 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{
+    core::HostsFileStructure,
+    entry::{EntrySource, HostEntry},
+    helpers::{create_hosts_manager, generate_backup_filename, path_backup_type},
+};
+use crate::core::filesystem::FileManager;
+use crate::core::i18n::t;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// 导出文件的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// 标准 hosts 格式
+    Hosts,
+    /// dnsmasq 配置：`host-record=example.com,0.0.0.0`
+    ///
+    /// 不使用 `address=/example.com/ip`，它同时匹配所有子域名，与 hosts 文件的语义不同。
+    Dnsmasq,
+    /// unbound 配置：`local-data: "example.com. A 0.0.0.0"`
+    Unbound,
+    /// CoreDNS 的 `hosts` 插件块
+    CorednsHosts,
+    /// JSON 数组，每个主机名一个对象
+    Json,
+}
+
+impl ExportFormat {
+    /// 所有格式的名称，用于命令行参数
+    pub const NAMES: [&'static str; 5] = ["hosts", "dnsmasq", "unbound", "coredns-hosts", "json"];
+
+    /// 将合并后的记录渲染为目标格式
+    fn render(self, records: &[ExportRecord]) -> Result<String> {
+        let mut output = String::new();
+        match self {
+            ExportFormat::Hosts => {
+                writeln!(output, "# {}", t!("command.hosts.export.header"))?;
+                for record in records {
+                    writeln!(output, "{} {}", record.ip, record.hostname)?;
+                }
+            }
+            ExportFormat::Dnsmasq => {
+                writeln!(output, "# {}", t!("command.hosts.export.header"))?;
+                for record in records {
+                    writeln!(output, "host-record={},{}", record.hostname, record.ip)?;
+                }
+            }
+            ExportFormat::Unbound => {
+                writeln!(output, "# {}", t!("command.hosts.export.header"))?;
+                writeln!(output, "server:")?;
+                for record in records {
+                    let record_type = if record.ip.is_ipv4() { "A" } else { "AAAA" };
+                    writeln!(
+                        output,
+                        "    local-data: \"{}. {record_type} {}\"",
+                        record.hostname, record.ip
+                    )?;
+                }
+            }
+            ExportFormat::CorednsHosts => {
+                writeln!(output, "# {}", t!("command.hosts.export.header"))?;
+                writeln!(output, "hosts {{")?;
+                for record in records {
+                    writeln!(output, "    {} {}", record.ip, record.hostname)?;
+                }
+                // 未列出的主机名交给后续插件解析
+                writeln!(output, "    fallthrough")?;
+                writeln!(output, "}}")?;
+            }
+            ExportFormat::Json => {
+                output = serde_json::to_string_pretty(records)?;
+                output.push('\n');
+            }
+        }
+        Ok(output)
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "hosts" => Ok(ExportFormat::Hosts),
+            "dnsmasq" => Ok(ExportFormat::Dnsmasq),
+            "unbound" => Ok(ExportFormat::Unbound),
+            "coredns-hosts" => Ok(ExportFormat::CorednsHosts),
+            "json" => Ok(ExportFormat::Json),
+            _ => anyhow::bail!(
+                "{}",
+                t!("error.hosts_invalid_export_format", format = value)
+            ),
+        }
+    }
+}
+
+/// 导出的单个主机名映射
+#[derive(Debug, Clone, Serialize)]
+struct ExportRecord {
+    hostname: String,
+    ip: IpAddr,
+    /// 来源：`local` 或订阅 URL
+    source: String,
+}
+
+/// 合并本地条目和所有已启用订阅的条目
+///
+/// 按 hosts 文件的生效顺序（本地条目优先，订阅按优先级排列）合并，
+/// 同一主机名在 IPv4 和 IPv6 中各只保留最先出现的映射。返回记录和被覆盖的映射数量。
+fn merge_entries(structure: &HostsFileStructure, config: &Config) -> (Vec<ExportRecord>, usize) {
+    let entries: Vec<HostEntry> = structure
+        .local_entries()
+        .into_iter()
+        .chain(
+            config
+                .get_hosts_subscriptions()
+                .iter()
+                .filter(|url| !config.is_subscription_disabled(url))
+                .flat_map(|url| structure.subscription_entries(url).unwrap_or_default()),
+        )
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut records = Vec::new();
+    let mut overridden = 0;
+    for entry in &entries {
+        let source = match &entry.source {
+            EntrySource::Subscription(url) => url.clone(),
+            _ => "local".to_string(),
+        };
+        for hostname in entry.hostnames() {
+            let hostname = hostname.to_lowercase();
+            if !seen.insert((hostname.clone(), entry.ip.is_ipv4())) {
+                overridden += 1;
+                continue;
+            }
+            records.push(ExportRecord {
+                hostname,
+                ip: entry.ip,
+                source: source.clone(),
+            });
+        }
+    }
+    (records, overridden)
+}
+
+/// 处理导出命令
+///
+/// 未指定 `output` 时输出到标准输出（状态信息输出到标准错误）；`apply` 时通过 `FileManager` 原子性写入 `output`，
+/// 写入前备份原有文件，内容未变化时不写入。
+pub fn handle_export(format: ExportFormat, output: Option<&str>, apply: bool) -> Result<()> {
+    let config = Config::load()?;
+    let structure: HostsFileStructure = create_hosts_manager()?.parse_file()?;
+    let (records, overridden) = merge_entries(&structure, &config);
+    let content = format.render(&records)?;
+
+    let statistics = t!(
+        "command.hosts.export.statistics",
+        count = records.len(),
+        overridden = overridden
+    );
+    let Some(output) = output else {
+        // 导出内容占用标准输出，状态信息全部输出到标准错误
+        print!("{content}");
+        eprintln!("{statistics}");
+        return Ok(());
+    };
+
+    let path = PathBuf::from(output);
+    if apply {
+        apply_export(&path, &content)?;
+    } else {
+        std::fs::write(&path, &content).map_err(|e| {
+            anyhow::anyhow!(
+                "{}",
+                t!(
+                    "error.hosts_export_write_failed",
+                    path = path.display(),
+                    error = e
+                )
+            )
+        })?;
+        println!(
+            "{}",
+            t!("command.hosts.export.written", path = path.display())
+        );
+    }
+
+    println!("{statistics}");
+    Ok(())
+}
+
+/// 备份原有文件后原子性写入导出内容，内容未变化时跳过
+fn apply_export(path: &Path, content: &str) -> Result<()> {
+    let file_manager = FileManager::with_typed_backup(
+        path.to_path_buf(),
+        &path_backup_type("export", &std::path::absolute(path)?),
+    )?;
+
+    if path.exists() {
+        if file_manager.read_file().ok().as_deref() == Some(content) {
+            println!(
+                "{}",
+                t!("command.hosts.export.unchanged", path = path.display())
+            );
+            return Ok(());
+        }
+        let backup = file_manager.backup_file(&generate_backup_filename())?;
+        println!(
+            "{}",
+            t!("command.hosts.export.backed_up", path = backup.display())
+        );
+    }
+
+    file_manager.write_file_atomic(content)?;
+    println!(
+        "{}",
+        t!("command.hosts.export.applied", path = path.display())
+    );
+    Ok(())
+}
@@ -0,0 +1,340 @@
+use crate::commands::config::{Config, model::SubscriptionOptions};
+use crate::commands::hosts::{entry::HostEntry, helpers::require_subscription_target};
+use crate::core::i18n::t;
+use crate::core::table::{add_table_row, create_subscription_table, print_table, set_table_header};
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+
+/// 正则表达式模式的前缀，其余模式按 glob 处理
+const REGEX_PATTERN_PREFIX: &str = "re:";
+
+/// 主机名匹配模式：默认为 glob（支持 `*` 和 `?`），以 `re:` 开头时为正则表达式
+///
+/// 两种模式都不区分大小写。
+#[derive(Debug, Clone)]
+pub enum HostnamePattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl HostnamePattern {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        match pattern.strip_prefix(REGEX_PATTERN_PREFIX) {
+            Some(expr) => RegexBuilder::new(expr)
+                .case_insensitive(true)
+                .build()
+                .map(HostnamePattern::Regex)
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "{}",
+                        t!(
+                            "error.hosts_invalid_filter_pattern",
+                            pattern = pattern,
+                            error = e
+                        )
+                    )
+                }),
+            None if pattern.trim().is_empty() => anyhow::bail!(
+                "{}",
+                t!(
+                    "error.hosts_invalid_filter_pattern",
+                    pattern = pattern,
+                    error = t!("error.hosts_empty_filter_pattern")
+                )
+            ),
+            None => Ok(HostnamePattern::Glob(pattern.trim().to_lowercase())),
+        }
+    }
+
+    pub fn matches(&self, hostname: &str) -> bool {
+        match self {
+            HostnamePattern::Glob(glob) => glob_matches(glob, &hostname.to_lowercase()),
+            HostnamePattern::Regex(regex) => regex.is_match(hostname),
+        }
+    }
+}
+
+/// 简单的 glob 匹配：`*` 匹配任意长度的字符（包括 `.`），`?` 匹配单个字符
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut s) = (0, 0);
+    // 最近一个 `*` 的位置，以及它当前匹配到的文本位置
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while s < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[s]) {
+            p += 1;
+            s += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, s));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            s = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 订阅的主机名过滤规则
+///
+/// 设置了 include 时只保留匹配其中任一模式的主机名；
+/// 匹配任一 exclude 模式的主机名总是被移除。
+#[derive(Debug, Clone, Default)]
+pub struct HostnameFilter {
+    include: Vec<(String, HostnamePattern)>,
+    exclude: Vec<(String, HostnamePattern)>,
+}
+
+/// 一次过滤的统计结果
+#[derive(Debug, Clone, Default)]
+pub struct FilterReport {
+    /// 不匹配任何 include 模式而被移除的主机名数量
+    pub not_included: usize,
+    /// 每个 exclude 模式移除的主机名数量，与配置顺序一致
+    pub excluded: Vec<(String, usize)>,
+}
+
+impl FilterReport {
+    /// 过滤掉的主机名总数
+    pub fn total(&self) -> usize {
+        self.not_included + self.excluded.iter().map(|(_, count)| count).sum::<usize>()
+    }
+
+    /// 打印每条规则移除的数量，没有移除任何内容时不输出
+    pub fn print(&self) {
+        if self.total() == 0 {
+            return;
+        }
+
+        println!(
+            "{}",
+            t!("command.hosts.filter.removed_total", count = self.total())
+        );
+        if self.not_included > 0 {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.filter.removed_include",
+                    count = self.not_included
+                )
+            );
+        }
+        for (pattern, count) in self.excluded.iter().filter(|(_, count)| *count > 0) {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.filter.removed_exclude",
+                    pattern = pattern,
+                    count = count
+                )
+            );
+        }
+    }
+}
+
+impl HostnameFilter {
+    /// 根据订阅选项构建过滤规则
+    pub fn from_options(options: &SubscriptionOptions) -> Result<Self> {
+        let compile = |patterns: &[String]| -> Result<Vec<(String, HostnamePattern)>> {
+            patterns
+                .iter()
+                .map(|pattern| Ok((pattern.clone(), HostnamePattern::parse(pattern)?)))
+                .collect()
+        };
+
+        Ok(Self {
+            include: compile(&options.include)?,
+            exclude: compile(&options.exclude)?,
+        })
+    }
+
+    /// 过滤条目中的主机名，返回保留的条目和每条规则移除的数量
+    ///
+    /// 规范名被移除时，由第一个保留的别名接替；所有主机名都被移除的条目整行丢弃。
+    pub fn apply(&self, entries: Vec<HostEntry>) -> (Vec<HostEntry>, FilterReport) {
+        let mut report = FilterReport {
+            not_included: 0,
+            excluded: self
+                .exclude
+                .iter()
+                .map(|(pattern, _)| (pattern.clone(), 0))
+                .collect(),
+        };
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return (entries, report);
+        }
+
+        let entries = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let mut kept: Vec<String> = entry
+                    .hostnames()
+                    .filter(|hostname| self.keeps(hostname, &mut report))
+                    .map(|hostname| hostname.to_string())
+                    .collect();
+                if kept.is_empty() {
+                    return None;
+                }
+                let canonical = kept.remove(0);
+                Some(HostEntry {
+                    canonical,
+                    aliases: kept,
+                    ..entry
+                })
+            })
+            .collect();
+
+        (entries, report)
+    }
+
+    /// 判断主机名是否保留，并记录移除它的规则
+    fn keeps(&self, hostname: &str, report: &mut FilterReport) -> bool {
+        if !self.include.is_empty()
+            && !self
+                .include
+                .iter()
+                .any(|(_, pattern)| pattern.matches(hostname))
+        {
+            report.not_included += 1;
+            return false;
+        }
+
+        match self
+            .exclude
+            .iter()
+            .position(|(_, pattern)| pattern.matches(hostname))
+        {
+            Some(index) => {
+                report.excluded[index].1 += 1;
+                false
+            }
+            None => true,
+        }
+    }
+}
+
+/// 处理添加过滤规则命令
+pub fn handle_filter_add(target: &str, include: &[String], exclude: &[String]) -> Result<()> {
+    if include.is_empty() && exclude.is_empty() {
+        anyhow::bail!("{}", t!("error.hosts_missing_filter_pattern"));
+    }
+
+    // 先验证所有模式，避免写入无效配置
+    for pattern in include.iter().chain(exclude) {
+        HostnamePattern::parse(pattern)?;
+    }
+
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+    let options = config.subscription_options_mut(&url)?;
+
+    let mut added = 0;
+    options.clear_fetch_state();
+    for (patterns, new_patterns) in [
+        (&mut options.include, include),
+        (&mut options.exclude, exclude),
+    ] {
+        for pattern in new_patterns {
+            if !patterns.contains(pattern) {
+                patterns.push(pattern.clone());
+                added += 1;
+            }
+        }
+    }
+    config.save()?;
+
+    println!(
+        "{}",
+        t!("command.hosts.filter.added", count = added, url = url)
+    );
+    println!("{}", t!("command.hosts.filter.update_hint"));
+    Ok(())
+}
+
+/// 处理移除过滤规则命令（同时从 include 和 exclude 中移除）
+pub fn handle_filter_remove(target: &str, pattern: &str) -> Result<()> {
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+    let options = config.subscription_options_mut(&url)?;
+
+    let before = options.include.len() + options.exclude.len();
+    options.include.retain(|p| p != pattern);
+    options.exclude.retain(|p| p != pattern);
+    let removed = before - options.include.len() - options.exclude.len();
+    options.clear_fetch_state();
+
+    if removed == 0 {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.filter.not_found",
+                pattern = pattern,
+                url = url
+            )
+        );
+        return Ok(());
+    }
+    config.save()?;
+
+    println!(
+        "{}",
+        t!("command.hosts.filter.removed", pattern = pattern, url = url)
+    );
+    println!("{}", t!("command.hosts.filter.update_hint"));
+    Ok(())
+}
+
+/// 处理过滤规则列表命令，未指定订阅时列出所有订阅的规则
+pub fn handle_filter_list(target: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let urls = match target {
+        Some(target) => vec![require_subscription_target(&config, target)?],
+        None => config.get_hosts_subscriptions(),
+    };
+
+    println!("{}", t!("command.hosts.filter.title"));
+    println!();
+
+    let mut table = create_subscription_table();
+    set_table_header(
+        &mut table,
+        vec![
+            t!("command.hosts.filter.table_header_subscription").to_string(),
+            t!("command.hosts.filter.table_header_kind").to_string(),
+            t!("command.hosts.filter.table_header_pattern").to_string(),
+        ],
+    );
+
+    let mut count = 0;
+    for url in &urls {
+        let options = config.get_subscription_options(url);
+        let rules = options
+            .include
+            .iter()
+            .map(|pattern| ("include", pattern))
+            .chain(options.exclude.iter().map(|pattern| ("exclude", pattern)));
+        for (kind, pattern) in rules {
+            add_table_row(
+                &mut table,
+                vec![url.clone(), kind.to_string(), pattern.clone()],
+            );
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        println!("{}", t!("command.hosts.filter.empty"));
+        return Ok(());
+    }
+
+    print_table(&table);
+    println!("{}", t!("command.hosts.filter.statistics", count = count));
+    Ok(())
+}
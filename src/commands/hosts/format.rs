@@ -0,0 +1,381 @@
+use crate::commands::hosts::entry::{EntrySource, HostEntry};
+use crate::core::i18n::t;
+use crate::core::validation::is_valid_hostname;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use std::str::FromStr;
+
+/// 屏蔽列表（域名列表、adblock、dnsmasq）转换为 hosts 条目时使用的黑洞地址
+const BLOCK_IP: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+/// 自动检测格式时采样的最大有效行数
+const DETECT_SAMPLE_LINES: usize = 500;
+
+/// 报告中最多列出的无法转换的行数
+const MAX_REPORTED_LINES: usize = 10;
+
+/// 转换为 hosts 条目时可以忽略的 adblock 规则修饰符（不影响屏蔽的域名）
+const ADBLOCK_IGNORED_MODIFIERS: &[&str] = &["important", "all", "document", "doc"];
+
+/// 订阅列表的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListFormat {
+    /// 根据内容自动检测
+    #[default]
+    Auto,
+    /// 标准 hosts 格式：`<ip> <hostname> [aliases...]`
+    Hosts,
+    /// 每行一个域名
+    Domains,
+    /// adblock 风格的域名规则：`||example.com^`
+    Adblock,
+    /// dnsmasq 配置：`address=/example.com/0.0.0.0`
+    Dnsmasq,
+}
+
+impl ListFormat {
+    /// 所有格式的名称，用于命令行参数
+    pub const NAMES: [&'static str; 5] = ["auto", "hosts", "domains", "adblock", "dnsmasq"];
+
+    /// 是否为自动检测（订阅选项的默认值）
+    pub fn is_auto(&self) -> bool {
+        *self == ListFormat::Auto
+    }
+
+    /// 根据内容检测格式：统计采样行能被各格式识别的数量，取最多的一种
+    ///
+    /// 标准 hosts 行在所有格式中都能识别，因此只在其他格式识别的行更多时才选择其他格式。
+    pub fn detect(content: &str) -> ListFormat {
+        // 数量相同时按候选顺序优先
+        let candidates = [
+            ListFormat::Hosts,
+            ListFormat::Domains,
+            ListFormat::Adblock,
+            ListFormat::Dnsmasq,
+        ];
+        let mut counts = [0usize; 4];
+
+        let sample = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !is_comment(line))
+            .take(DETECT_SAMPLE_LINES);
+        for line in sample {
+            for (format, count) in candidates.iter().zip(counts.iter_mut()) {
+                if matches!(
+                    format.convert_line(line, &EntrySource::Unmanaged),
+                    LineResult::Entries(_) | LineResult::DomainEntries(_)
+                ) {
+                    *count += 1;
+                }
+            }
+        }
+
+        // 按候选顺序取第一个最大值
+        let mut best = 0;
+        for (index, count) in counts.iter().enumerate() {
+            if *count > counts[best] {
+                best = index;
+            }
+        }
+        candidates[best]
+    }
+
+    /// 自动检测时返回检测到的格式，否则返回自身
+    pub fn resolve(self, content: &str) -> ListFormat {
+        match self {
+            ListFormat::Auto => ListFormat::detect(content),
+            format => format,
+        }
+    }
+
+    /// 将订阅内容转换为 hosts 条目，并记录无法转换的行
+    ///
+    /// 所有格式都接受标准 hosts 行，因此已经标准化的缓存内容可以用任意格式重新处理。
+    pub fn convert(self, content: &str, source: &EntrySource) -> (Vec<HostEntry>, FormatReport) {
+        let format = self.resolve(content);
+        let mut entries = Vec::new();
+        let mut report = FormatReport {
+            format,
+            ..FormatReport::default()
+        };
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || is_comment(line) {
+                continue;
+            }
+            let converted = match format.convert_line(line, source) {
+                LineResult::Entries(converted) => converted,
+                LineResult::DomainEntries(converted) => {
+                    report.subdomain_rules += 1;
+                    converted
+                }
+                LineResult::Ignored => continue,
+                LineResult::Unconvertible => {
+                    report.skipped.push(SkippedLine {
+                        line_num: index + 1,
+                        line: line.to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            report.converted_lines += 1;
+            if converted
+                .iter()
+                .flat_map(HostEntry::hostnames)
+                .any(|hostname| !is_valid_hostname(hostname))
+            {
+                report.invalid_hostnames.push(SkippedLine {
+                    line_num: index + 1,
+                    line: line.to_string(),
+                });
+            }
+            entries.extend(converted);
+        }
+
+        (entries, report)
+    }
+
+    /// 转换单行内容（已去除首尾空白，不是空行或 `#` 注释）
+    fn convert_line(self, line: &str, source: &EntrySource) -> LineResult {
+        // 所有格式都接受标准 hosts 行
+        if let Some(entry) = HostEntry::parse(line, source.clone()) {
+            return LineResult::Entries(vec![entry]);
+        }
+
+        match self {
+            ListFormat::Auto | ListFormat::Hosts => LineResult::Unconvertible,
+            ListFormat::Domains => convert_domain_line(line, source),
+            ListFormat::Adblock => convert_adblock_line(line, source),
+            ListFormat::Dnsmasq => convert_dnsmasq_line(line, source),
+        }
+    }
+}
+
+impl fmt::Display for ListFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ListFormat::Auto => "auto",
+            ListFormat::Hosts => "hosts",
+            ListFormat::Domains => "domains",
+            ListFormat::Adblock => "adblock",
+            ListFormat::Dnsmasq => "dnsmasq",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for ListFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(ListFormat::Auto),
+            "hosts" => Ok(ListFormat::Hosts),
+            "domains" => Ok(ListFormat::Domains),
+            "adblock" => Ok(ListFormat::Adblock),
+            "dnsmasq" => Ok(ListFormat::Dnsmasq),
+            _ => anyhow::bail!("{}", t!("error.hosts_invalid_format", format = value)),
+        }
+    }
+}
+
+/// 单行的转换结果
+enum LineResult {
+    /// 转换得到的条目
+    Entries(Vec<HostEntry>),
+    /// 由同时匹配子域名的规则转换得到的条目（hosts 条目只匹配主机名本身）
+    DomainEntries(Vec<HostEntry>),
+    /// 格式自身的元信息（如 adblock 的 `[Adblock Plus 2.0]` 头），无需转换
+    Ignored,
+    /// 无法用 hosts 条目表达的行
+    Unconvertible,
+}
+
+/// 无法转换为 hosts 条目的行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedLine {
+    /// 行号（从 1 开始）
+    pub line_num: usize,
+    pub line: String,
+}
+
+/// 格式转换的统计结果
+#[derive(Debug, Clone, Default)]
+pub struct FormatReport {
+    /// 实际使用的格式（自动检测时为检测结果）
+    pub format: ListFormat,
+    /// 成功转换的行数
+    pub converted_lines: usize,
+    /// 无法转换的行
+    pub skipped: Vec<SkippedLine>,
+    /// 已转换但包含无效主机名的行（严格验证时拒绝）
+    pub invalid_hostnames: Vec<SkippedLine>,
+    /// 原规则同时匹配子域名的行数，转换后不再覆盖子域名
+    pub subdomain_rules: usize,
+}
+
+impl FormatReport {
+    /// 打印非 hosts 格式的转换统计和无法转换的行，标准 hosts 内容且没有无效行时不输出
+    pub fn print(&self) {
+        if !matches!(self.format, ListFormat::Hosts | ListFormat::Auto) {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.format.converted",
+                    format = self.format,
+                    count = self.converted_lines
+                )
+            );
+        }
+        if self.subdomain_rules > 0 {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.format.subdomains_lost",
+                    count = self.subdomain_rules,
+                    format = self.format
+                )
+            );
+        }
+        if self.skipped.is_empty() {
+            return;
+        }
+
+        println!(
+            "{}",
+            t!(
+                "command.hosts.format.skipped",
+                count = self.skipped.len(),
+                format = self.format
+            )
+        );
+        for skipped in self.skipped.iter().take(MAX_REPORTED_LINES) {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.format.skipped_line",
+                    line_num = skipped.line_num,
+                    line = skipped.line
+                )
+            );
+        }
+        if self.skipped.len() > MAX_REPORTED_LINES {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.format.skipped_more",
+                    count = self.skipped.len() - MAX_REPORTED_LINES
+                )
+            );
+        }
+    }
+}
+
+/// 是否为所有格式通用的注释行（`#`），adblock 的 `!` 注释也视为注释
+fn is_comment(line: &str) -> bool {
+    line.starts_with('#') || line.starts_with('!')
+}
+
+/// 屏蔽指定主机名的条目
+fn block_entry(hostname: &str, source: &EntrySource) -> HostEntry {
+    HostEntry {
+        ip: BLOCK_IP,
+        canonical: hostname.to_ascii_lowercase(),
+        aliases: Vec::new(),
+        comment: None,
+        source: source.clone(),
+    }
+}
+
+/// 域名列表：每行一个域名，可带 `#` 行尾注释
+fn convert_domain_line(line: &str, source: &EntrySource) -> LineResult {
+    let domain = line.split('#').next().unwrap_or_default().trim();
+    if is_valid_hostname(domain) {
+        LineResult::Entries(vec![block_entry(domain, source)])
+    } else {
+        LineResult::Unconvertible
+    }
+}
+
+/// adblock 规则：只能转换屏蔽整个域名的 `||example.com^` 规则
+///
+/// 没有 `||` 前缀的行是 URL 子串过滤规则，与例外规则（`@@`）、通配符、路径匹配、元素隐藏
+/// 和会改变匹配范围的修饰符一样无法用 hosts 表达。`||` 规则同时屏蔽子域名，转换后只屏蔽该域名本身。
+fn convert_adblock_line(line: &str, source: &EntrySource) -> LineResult {
+    if line.starts_with('[') && line.ends_with(']') {
+        return LineResult::Ignored;
+    }
+
+    let (rule, modifiers) = match line.split_once('$') {
+        Some((rule, modifiers)) => (rule, Some(modifiers)),
+        None => (line, None),
+    };
+    if let Some(modifiers) = modifiers
+        && !modifiers
+            .split(',')
+            .all(|modifier| ADBLOCK_IGNORED_MODIFIERS.contains(&modifier.trim()))
+    {
+        return LineResult::Unconvertible;
+    }
+
+    match rule
+        .strip_prefix("||")
+        .and_then(|rule| rule.strip_suffix('^'))
+    {
+        Some(domain) if is_valid_hostname(domain) => {
+            LineResult::DomainEntries(vec![block_entry(domain, source)])
+        }
+        _ => LineResult::Unconvertible,
+    }
+}
+
+/// dnsmasq 配置：`address=/a.com/b.com/<ip>`，以及将域名解析为 NXDOMAIN 的
+/// `address=/a.com/`、`server=/a.com/`、`local=/a.com/`
+///
+/// 转发到其他服务器的 `server=/a.com/<upstream>` 和其他配置项无法用 hosts 表达。
+/// 这些规则同时匹配子域名，转换后只匹配该域名本身。
+fn convert_dnsmasq_line(line: &str, source: &EntrySource) -> LineResult {
+    let Some((key, value)) = line.split_once('=') else {
+        return LineResult::Unconvertible;
+    };
+    let key = key.trim();
+    if !matches!(key, "address" | "server" | "local") {
+        return LineResult::Unconvertible;
+    }
+
+    // `/a.com/b.com/<target>`：最后一段为目标，之前为域名
+    let Some(value) = value.trim().strip_prefix('/') else {
+        return LineResult::Unconvertible;
+    };
+    let Some((domains, target)) = value.rsplit_once('/') else {
+        return LineResult::Unconvertible;
+    };
+    let ip = match (key, target.trim()) {
+        (_, "" | "#") => BLOCK_IP,
+        ("address", target) => match target.parse::<IpAddr>() {
+            Ok(ip) => ip,
+            Err(_) => return LineResult::Unconvertible,
+        },
+        _ => return LineResult::Unconvertible,
+    };
+
+    let domains: Vec<&str> = domains.split('/').collect();
+    if domains.is_empty() || !domains.iter().all(|domain| is_valid_hostname(domain)) {
+        return LineResult::Unconvertible;
+    }
+    LineResult::DomainEntries(
+        domains
+            .into_iter()
+            .map(|domain| HostEntry {
+                ip,
+                ..block_entry(domain, source)
+            })
+            .collect(),
+    )
+}
@@ -1,19 +1,169 @@
-use crate::core::filesystem::{FileManager, StructuredFileManager};
+use crate::commands::config::Config;
+use crate::commands::hosts::core::HostsFileStructure;
+use crate::commands::hosts::entry::EntrySource;
+use crate::commands::hosts::format::ListFormat;
+use crate::core::diff::unified_diff;
+use crate::core::file_source::normalize_source;
+use crate::core::filesystem::{FileManager, FileStructure, StructuredFileManager};
 use crate::core::globals::{
-    BACKUP_FILE_PREFIX, BACKUP_FILE_SUFFIX, MAX_PREVIEW_LINE_LENGTH, MAX_PREVIEW_LINES,
-    PREVIEW_LINE_DISPLAY_LENGTH,
+    BACKUP_FILE_PREFIX, BACKUP_FILE_SUFFIX, DEFAULT_HOSTS_FILE_PATH, HOSTS_FILE_ENV_VAR,
+    HOSTS_FILE_OVERRIDE, MAX_PREVIEW_LINE_LENGTH, MAX_PREVIEW_LINES, PREVIEW_LINE_DISPLAY_LENGTH,
 };
 use crate::core::i18n::t;
+use crate::core::permission::ensure_sudo_privileges;
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// 创建 hosts 文件管理器（统一函数）
 pub fn create_hosts_manager() -> Result<StructuredFileManager> {
-    let file_manager = FileManager::with_typed_backup(PathBuf::from("/etc/hosts"), "hosts")?;
+    let hosts_path = get_hosts_file_path()?;
+    let backup_type = hosts_backup_type(&hosts_path);
+    let file_manager = FileManager::with_typed_backup(hosts_path, &backup_type)?;
     Ok(StructuredFileManager::new(file_manager))
 }
 
+/// 设置命令行指定的 hosts 文件路径
+pub fn set_hosts_file_override(path: &str) {
+    let _ = HOSTS_FILE_OVERRIDE.set(PathBuf::from(path));
+}
+
+/// 获取目标 hosts 文件路径
+///
+/// 优先级：`--hosts-file` > `XDEV_HOSTS_FILE` 环境变量 > 配置 `hosts_file` > /etc/hosts
+pub fn get_hosts_file_path() -> Result<PathBuf> {
+    if let Some(path) = HOSTS_FILE_OVERRIDE.get() {
+        return Ok(path.clone());
+    }
+
+    if let Ok(path) = std::env::var(HOSTS_FILE_ENV_VAR)
+        && !path.trim().is_empty()
+    {
+        return Ok(PathBuf::from(path.trim()));
+    }
+
+    let config = Config::load()?;
+    if let Some(path) = config.hosts_file.as_deref()
+        && !path.trim().is_empty()
+    {
+        return Ok(PathBuf::from(path.trim()));
+    }
+
+    Ok(PathBuf::from(DEFAULT_HOSTS_FILE_PATH))
+}
+
+/// 是否为系统默认的 hosts 文件
+pub fn is_default_hosts_file(path: &Path) -> bool {
+    path == Path::new(DEFAULT_HOSTS_FILE_PATH)
+}
+
+/// 根据目标文件生成备份分类，不同目标的备份互不混合
+fn hosts_backup_type(path: &Path) -> String {
+    if is_default_hosts_file(path) {
+        return "hosts".to_string();
+    }
+    path_backup_type("hosts", path)
+}
+
+/// 由分类前缀和文件路径生成备份分类（路径中的非字母数字字符替换为 `_`）
+pub fn path_backup_type(prefix: &str, path: &Path) -> String {
+    let sanitized: String = path
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{prefix}-{}", sanitized.trim_matches('_'))
+}
+
+/// 确保可以写入目标 hosts 文件
+///
+/// 当前用户可直接写入目标文件时（如测试用的副本）不再要求 sudo 权限
+pub fn ensure_hosts_write_permission() -> Result<()> {
+    let hosts_path = get_hosts_file_path()?;
+    let writable = std::fs::OpenOptions::new()
+        .write(true)
+        .open(&hosts_path)
+        .is_ok();
+
+    if writable {
+        return Ok(());
+    }
+
+    ensure_sudo_privileges()
+}
+
+/// 按配置中的订阅优先级排列订阅块，然后备份并写入 hosts 文件
+pub fn save_hosts_structure(
+    hosts_manager: &StructuredFileManager,
+    structure: &mut HostsFileStructure,
+) -> Result<()> {
+    let config = Config::load()?;
+    structure.sort_subscriptions(&config.get_hosts_subscriptions());
+
+    let backup_filename = generate_backup_filename();
+    hosts_manager.update_structure_with_backup(structure, &backup_filename)
+}
+
+/// 预览保存后的 hosts 文件：按订阅优先级排列后与当前文件对比，打印统一差异
+///
+/// 只读取目标文件，不备份、不写入，因此不需要写入权限。
+pub fn preview_hosts_structure(
+    hosts_manager: &StructuredFileManager,
+    structure: &mut HostsFileStructure,
+) -> Result<()> {
+    let config = Config::load()?;
+    structure.sort_subscriptions(&config.get_hosts_subscriptions());
+
+    let file_manager = hosts_manager.file_manager();
+    let current = file_manager.read_file()?;
+    let label = file_manager.file_path.display().to_string();
+
+    println!("{}", t!("command.hosts.dry_run.title"));
+    match unified_diff(
+        &current,
+        &structure.reconstruct(),
+        &label,
+        &format!("{label} (dry-run)"),
+    ) {
+        Some(diff) => print!("{diff}"),
+        None => println!("{}", t!("command.hosts.dry_run.no_changes")),
+    }
+    println!("{}", t!("command.hosts.dry_run.not_written"));
+
+    Ok(())
+}
+
+/// 根据 URL、`hosts list` 中显示的序号、订阅名称或本地路径查找订阅 URL
+pub fn resolve_subscription_target(config: &Config, target: &str) -> Option<String> {
+    let subscriptions = config.get_hosts_subscriptions();
+    if let Some(url) = subscriptions.iter().find(|url| url.as_str() == target) {
+        return Some(url.clone());
+    }
+
+    target
+        .parse::<usize>()
+        .ok()
+        .filter(|index| *index >= 1)
+        .and_then(|index| subscriptions.get(index - 1))
+        .cloned()
+        .or_else(|| config.find_subscription_by_name(target))
+        .or_else(|| {
+            normalize_source(target)
+                .ok()
+                .filter(|url| subscriptions.contains(url))
+        })
+}
+
+/// 查找订阅 URL，未找到时返回错误
+pub fn require_subscription_target(config: &Config, target: &str) -> Result<String> {
+    resolve_subscription_target(config, target).ok_or_else(|| {
+        anyhow::anyhow!(
+            "{}",
+            t!("error.hosts_subscription_not_found", target = target)
+        )
+    })
+}
+
 /// 获取当前时间戳
 pub fn get_current_timestamp() -> u64 {
     SystemTime::now()
@@ -22,6 +172,50 @@ pub fn get_current_timestamp() -> u64 {
         .as_secs()
 }
 
+/// 解析时长字符串（如 `90s`、`30m`、`2h`、`1d`），返回秒数
+pub fn parse_duration(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let invalid = || anyhow::anyhow!("{}", t!("error.hosts_invalid_duration", value = value));
+
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    let multiplier = match unit {
+        "s" => 1,
+        "" | "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+
+    match number.checked_mul(multiplier) {
+        Some(seconds) if seconds > 0 => Ok(seconds),
+        _ => Err(invalid()),
+    }
+}
+
+/// 将秒数格式化为易读的时长（如 `1h20m`）
+pub fn format_duration(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let hours = seconds % 86400 / 3600;
+    let minutes = seconds % 3600 / 60;
+
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{days}d"));
+    }
+    if hours > 0 {
+        parts.push(format!("{hours}h"));
+    }
+    if minutes > 0 || parts.is_empty() {
+        parts.push(format!("{}m", minutes.max(1)));
+    }
+    parts.join("")
+}
+
 /// 生成备份文件名
 pub fn generate_backup_filename() -> String {
     let timestamp = get_current_timestamp();
@@ -29,32 +223,23 @@ pub fn generate_backup_filename() -> String {
 }
 
 /// 显示下载内容的预览
-pub fn print_content_preview(content: &str) {
+pub fn print_content_preview(content: &str, format: ListFormat) {
     println!("{}", t!("command.hosts.content_preview.title"));
 
-    let lines: Vec<&str> = content.lines().collect();
-    let total_lines = lines.len();
-    let mut valid_entries = 0;
-    let mut preview_count = 0;
-
-    for line in &lines {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
+    let total_lines = content.lines().count();
+    let (entries, _) = format.convert(content, &EntrySource::Unmanaged);
+    let valid_entries = entries.len();
 
-        valid_entries += 1;
-
-        if preview_count < MAX_PREVIEW_LINES {
-            // 截取过长的行
-            let display_line = if line.len() > MAX_PREVIEW_LINE_LENGTH {
-                format!("{}...", &line[..PREVIEW_LINE_DISPLAY_LENGTH])
-            } else {
-                line.to_string()
-            };
-            println!("  📍 {display_line}");
-            preview_count += 1;
-        }
+    for entry in entries.iter().take(MAX_PREVIEW_LINES) {
+        // 截取过长的行
+        let line = entry.to_line();
+        let display_line = if line.chars().count() > MAX_PREVIEW_LINE_LENGTH {
+            let truncated: String = line.chars().take(PREVIEW_LINE_DISPLAY_LENGTH).collect();
+            format!("{truncated}...")
+        } else {
+            line
+        };
+        println!("  📍 {display_line}");
     }
 
     if valid_entries > MAX_PREVIEW_LINES {
@@ -78,7 +263,12 @@ pub fn print_content_preview(content: &str) {
 }
 
 /// 显示更新结果摘要
-pub fn display_update_summary(success_count: usize, failed_urls: &[String], total_count: usize) {
+pub fn display_update_summary(
+    success_count: usize,
+    unchanged_count: usize,
+    failed_urls: &[String],
+    total_count: usize,
+) {
     println!("{}", t!("command.hosts.update.summary_title"));
     println!(
         "{}",
@@ -91,6 +281,13 @@ pub fn display_update_summary(success_count: usize, failed_urls: &[String], tota
             count = success_count
         )
     );
+    println!(
+        "{}",
+        t!(
+            "command.hosts.update.summary_unchanged",
+            count = unchanged_count
+        )
+    );
     println!(
         "{}",
         t!(
@@ -112,10 +309,10 @@ pub fn display_update_summary(success_count: usize, failed_urls: &[String], tota
         println!("{}", t!("command.hosts.update.suggestion_resubscribe"));
     }
 
-    if success_count == total_count {
+    if success_count + unchanged_count == total_count {
         println!();
         println!("{}", t!("command.hosts.update.all_success"));
-    } else if success_count > 0 {
+    } else if success_count + unchanged_count > 0 {
         println!();
         println!("{}", t!("command.hosts.update.partial_success"));
     } else {
@@ -0,0 +1,364 @@
+use crate::commands::config::{Config, model::SubscriptionOptions};
+use crate::commands::hosts::{cache, helpers::require_subscription_target};
+use crate::core::file_source::file_source_path;
+use crate::core::http::{CacheValidators, FetchResult, HttpClient};
+use crate::core::i18n::t;
+use anyhow::Result;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use blake2::Blake2b512;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+/// minisign 公钥和签名中的算法标识：直接签名原始内容
+const MINISIGN_ALG_PURE: &[u8; 2] = b"Ed";
+/// minisign 签名中的算法标识：签名内容的 BLAKE2b-512 哈希（minisign 0.10 起的默认值）
+const MINISIGN_ALG_HASHED: &[u8; 2] = b"ED";
+/// minisign 注释行的前缀
+const MINISIGN_UNTRUSTED_PREFIX: &str = "untrusted comment:";
+const MINISIGN_TRUSTED_PREFIX: &str = "trusted comment: ";
+
+/// 未配置签名地址时，minisign 签名文件的默认后缀
+const MINISIGN_SIGNATURE_SUFFIX: &str = ".minisig";
+/// 未配置签名地址时，ed25519 签名文件的默认后缀
+const ED25519_SIGNATURE_SUFFIX: &str = ".sig";
+
+/// 计算内容的 SHA-256（小写十六进制）
+pub fn content_sha256(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// 验证并规范化 SHA-256 固定值（64 位十六进制，统一为小写）
+pub fn validate_sha256(value: &str) -> Result<String> {
+    let hash = value.trim().to_lowercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("{}", t!("error.hosts_invalid_sha256", value = value));
+    }
+    Ok(hash)
+}
+
+/// 验证签名使用的公钥
+#[derive(Debug, Clone)]
+pub enum PublicKey {
+    /// 原始 ed25519 公钥（base64 编码的 32 字节），签名文件为 base64 编码的 64 字节签名
+    Ed25519(VerifyingKey),
+    /// minisign 公钥，签名文件为 `.minisig` 格式
+    Minisign { key_id: [u8; 8], key: VerifyingKey },
+}
+
+impl PublicKey {
+    /// 解析公钥：minisign 公钥（可包含 `untrusted comment` 行）或 base64 编码的 ed25519 公钥
+    pub fn parse(value: &str) -> Result<Self> {
+        let invalid = || anyhow::anyhow!("{}", t!("error.hosts_invalid_public_key"));
+
+        let encoded = key_line(value).ok_or_else(invalid)?;
+        let bytes = BASE64.decode(encoded).map_err(|_| invalid())?;
+
+        match bytes.len() {
+            32 => Ok(PublicKey::Ed25519(
+                verifying_key(&bytes).ok_or_else(invalid)?,
+            )),
+            42 if &bytes[..2] == MINISIGN_ALG_PURE => Ok(PublicKey::Minisign {
+                key_id: bytes[2..10].try_into().map_err(|_| invalid())?,
+                key: verifying_key(&bytes[10..]).ok_or_else(invalid)?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// 签名文件的默认地址
+    fn default_signature_url(&self, url: &str) -> String {
+        match self {
+            PublicKey::Ed25519(_) => format!("{url}{ED25519_SIGNATURE_SUFFIX}"),
+            PublicKey::Minisign { .. } => format!("{url}{MINISIGN_SIGNATURE_SUFFIX}"),
+        }
+    }
+
+    /// 验证内容的分离签名
+    fn verify(&self, content: &[u8], signature: &str) -> Result<()> {
+        let invalid = |reason: &str| {
+            anyhow::anyhow!("{}", t!("error.hosts_signature_invalid", reason = reason))
+        };
+
+        match self {
+            PublicKey::Ed25519(key) => {
+                let bytes = BASE64
+                    .decode(signature.trim())
+                    .map_err(|_| invalid("base64"))?;
+                let signature = Signature::from_slice(&bytes).map_err(|_| invalid("length"))?;
+                key.verify_strict(content, &signature)
+                    .map_err(|_| invalid("ed25519"))
+            }
+            PublicKey::Minisign { key_id, key } => {
+                let mut lines = signature.lines().map(str::trim).filter(|line| {
+                    !line.is_empty() && !line.starts_with(MINISIGN_UNTRUSTED_PREFIX)
+                });
+                let bytes = lines
+                    .next()
+                    .and_then(|line| BASE64.decode(line).ok())
+                    .filter(|bytes| bytes.len() == 74)
+                    .ok_or_else(|| invalid("minisig"))?;
+                if &bytes[2..10] != key_id {
+                    return Err(invalid("key id"));
+                }
+                let signature =
+                    Signature::from_slice(&bytes[10..]).map_err(|_| invalid("length"))?;
+
+                let message = match &bytes[..2] {
+                    alg if alg == MINISIGN_ALG_PURE => content.to_vec(),
+                    alg if alg == MINISIGN_ALG_HASHED => Blake2b512::digest(content).to_vec(),
+                    _ => return Err(invalid("algorithm")),
+                };
+                key.verify_strict(&message, &signature)
+                    .map_err(|_| invalid("ed25519"))?;
+
+                // 可信注释由全局签名保护，防止被篡改
+                let trusted_comment = lines
+                    .next()
+                    .and_then(|line| line.strip_prefix(MINISIGN_TRUSTED_PREFIX))
+                    .ok_or_else(|| invalid("trusted comment"))?;
+                let global_signature = lines
+                    .next()
+                    .and_then(|line| BASE64.decode(line).ok())
+                    .and_then(|bytes| Signature::from_slice(&bytes).ok())
+                    .ok_or_else(|| invalid("global signature"))?;
+                let mut global_message = bytes[10..].to_vec();
+                global_message.extend_from_slice(trusted_comment.as_bytes());
+                key.verify_strict(&global_message, &global_signature)
+                    .map_err(|_| invalid("global signature"))
+            }
+        }
+    }
+}
+
+/// 公钥文本中的密钥行（最后一个非注释行）
+fn key_line(value: &str) -> Option<&str> {
+    value
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty() && !line.starts_with(MINISIGN_UNTRUSTED_PREFIX))
+}
+
+fn verifying_key(bytes: &[u8]) -> Option<VerifyingKey> {
+    VerifyingKey::from_bytes(bytes.try_into().ok()?).ok()
+}
+
+/// 订阅内容的完整性校验规则：固定的 SHA-256 和分离签名
+///
+/// 校验和与签名针对发布的文件本身，即解压和解码前的原始字节（`.gz` 列表校验压缩文件）。
+#[derive(Debug, Clone, Default)]
+pub struct Integrity {
+    pinned_sha256: Option<String>,
+    public_key: Option<PublicKey>,
+    signature_url: Option<String>,
+    /// 内容与固定的 SHA-256 不一致时是否接受新内容
+    accept_new: bool,
+}
+
+impl Integrity {
+    /// 根据订阅选项构建校验规则
+    pub fn from_options(options: &SubscriptionOptions, accept_new: bool) -> Result<Self> {
+        Ok(Self {
+            pinned_sha256: options.sha256.clone(),
+            public_key: options
+                .public_key
+                .as_deref()
+                .map(PublicKey::parse)
+                .transpose()?,
+            signature_url: options.signature_url.clone(),
+            accept_new,
+        })
+    }
+
+    /// 校验下载的原始字节
+    ///
+    /// 内容与固定的 SHA-256 不一致时失败（`accept_new` 时只给出提示）；
+    /// 配置了公钥时下载签名文件并验证。
+    pub fn verify(&self, http_client: &HttpClient, url: &str, content: &[u8]) -> Result<()> {
+        if let Some(pinned) = &self.pinned_sha256 {
+            let actual = content_sha256(content);
+            if actual == *pinned {
+                println!("{}", t!("command.hosts.integrity.checksum_ok", url = url));
+            } else if self.accept_new {
+                println!(
+                    "{}",
+                    t!(
+                        "command.hosts.integrity.checksum_accepted",
+                        old = pinned,
+                        new = actual,
+                        url = url
+                    )
+                );
+            } else {
+                anyhow::bail!(
+                    "{}",
+                    t!(
+                        "error.hosts_checksum_mismatch",
+                        expected = pinned,
+                        actual = actual
+                    )
+                );
+            }
+        }
+
+        if let Some(key) = &self.public_key {
+            let signature_url = self
+                .signature_url
+                .clone()
+                .unwrap_or_else(|| key.default_signature_url(url));
+            let signature = fetch_signature(http_client, &signature_url)?;
+            key.verify(content, &signature)?;
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.integrity.signature_ok",
+                    signature_url = signature_url,
+                    url = url
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    /// `accept_new` 时内容对应的新 SHA-256 固定值，无需更新固定值时返回 None
+    pub fn new_pin(&self, content: &[u8]) -> Option<String> {
+        let pinned = self.pinned_sha256.as_ref().filter(|_| self.accept_new)?;
+        Some(content_sha256(content)).filter(|actual| actual != pinned)
+    }
+}
+
+/// 下载签名文件（支持 `file://` 地址）
+fn fetch_signature(http_client: &HttpClient, signature_url: &str) -> Result<String> {
+    let failed = |e: &dyn std::fmt::Display| {
+        anyhow::anyhow!(
+            "{}",
+            t!(
+                "error.hosts_signature_fetch_failed",
+                url = signature_url,
+                error = e
+            )
+        )
+    };
+
+    if let Some(path) = file_source_path(signature_url) {
+        return std::fs::read_to_string(path).map_err(|e| failed(&e));
+    }
+    match http_client
+        .fetch(signature_url, &CacheValidators::default())
+        .map_err(|e| failed(&e))?
+        .value
+    {
+        FetchResult::Modified { content, .. } => Ok(content),
+        FetchResult::NotModified { .. } => Err(failed(&"304 Not Modified")),
+    }
+}
+
+/// 处理固定校验和命令
+///
+/// 未指定 `sha256` 时固定缓存的原始下载内容的校验和；`clear` 时取消固定。
+pub fn handle_pin(target: &str, sha256: Option<&str>, clear: bool) -> Result<()> {
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+
+    let pinned = match (clear, sha256) {
+        (true, _) => None,
+        (false, Some(hash)) => Some(validate_sha256(hash)?),
+        (false, None) => Some(
+            cache::load_raw(&url)?
+                .map(|raw| content_sha256(&raw))
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.hosts_cache_missing", url = url)))?,
+        ),
+    };
+
+    let options = config.subscription_options_mut(&url)?;
+    options.sha256 = pinned.clone();
+    // 强制下次更新重新下载并校验
+    options.clear_fetch_state();
+    config.save()?;
+
+    match pinned {
+        Some(hash) => println!(
+            "{}",
+            t!("command.hosts.integrity.pinned", url = url, sha256 = hash)
+        ),
+        None => println!("{}", t!("command.hosts.integrity.unpinned", url = url)),
+    }
+    Ok(())
+}
+
+/// 处理签名公钥命令
+///
+/// 未指定公钥和签名地址时显示当前设置；`clear` 时取消签名验证。
+pub fn handle_public_key(
+    target: &str,
+    key: Option<&str>,
+    signature_url: Option<&str>,
+    clear: bool,
+) -> Result<()> {
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+
+    if key.is_none() && signature_url.is_none() && !clear {
+        print_integrity_settings(&config, &url);
+        return Ok(());
+    }
+
+    // 先验证公钥，避免写入无效配置
+    if let Some(key) = key {
+        PublicKey::parse(key)?;
+    }
+
+    let options = config.subscription_options_mut(&url)?;
+    if clear {
+        options.public_key = None;
+        options.signature_url = None;
+    }
+    if let Some(key) = key {
+        options.public_key = Some(key.trim().to_string());
+    }
+    if let Some(signature_url) = signature_url {
+        options.signature_url = Some(signature_url.trim().to_string()).filter(|s| !s.is_empty());
+    }
+    options.clear_fetch_state();
+    config.save()?;
+
+    println!("{}", t!("command.hosts.integrity.key_updated", url = url));
+    print_integrity_settings(&config, &url);
+    Ok(())
+}
+
+/// 显示订阅当前的校验设置
+fn print_integrity_settings(config: &Config, url: &str) {
+    let options = config.get_subscription_options(url);
+    let none = t!("command.hosts.label.none");
+
+    println!(
+        "{}",
+        t!(
+            "command.hosts.integrity.current_sha256",
+            value = options.sha256.as_deref().unwrap_or(&none)
+        )
+    );
+    let key = options.public_key.as_deref().and_then(key_line);
+    println!(
+        "{}",
+        t!(
+            "command.hosts.integrity.current_key",
+            value = key.unwrap_or(&none)
+        )
+    );
+    let signature_url = match (&options.signature_url, &options.public_key) {
+        (Some(signature_url), _) => signature_url.clone(),
+        (None, Some(key)) => PublicKey::parse(key)
+            .map(|key| key.default_signature_url(url))
+            .unwrap_or_default(),
+        (None, None) => none.to_string(),
+    };
+    println!(
+        "{}",
+        t!(
+            "command.hosts.integrity.current_signature_url",
+            value = signature_url
+        )
+    );
+}
@@ -0,0 +1,112 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::helpers::require_subscription_target;
+use crate::core::i18n::t;
+use anyhow::Result;
+
+/// 验证订阅名称：不能为空、不能包含空白字符、不能是纯数字（会与序号混淆），
+/// 也不能与其他订阅的名称重复
+pub fn validate_subscription_name(config: &Config, url: &str, name: &str) -> Result<()> {
+    if name.is_empty() || name.chars().any(char::is_whitespace) {
+        anyhow::bail!("{}", t!("error.hosts_invalid_name", name = name));
+    }
+    if name.parse::<usize>().is_ok() {
+        anyhow::bail!("{}", t!("error.hosts_numeric_name", name = name));
+    }
+    if let Some(other) = config
+        .find_subscription_by_name(name)
+        .filter(|other| other != url)
+    {
+        anyhow::bail!(
+            "{}",
+            t!("error.hosts_name_in_use", name = name, url = other)
+        );
+    }
+    Ok(())
+}
+
+/// 添加订阅标签，忽略已存在的标签，返回新增的数量
+pub fn add_tags(tags: &mut Vec<String>, new_tags: &[String]) -> usize {
+    let mut added = 0;
+    for tag in new_tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+            added += 1;
+        }
+    }
+    added
+}
+
+/// 处理标注命令：设置订阅名称和备注、添加或移除标签
+///
+/// `name` 或 `notes` 为空字符串时清除对应的值。所有参数都未指定时显示当前的名称、备注和标签。
+pub fn handle_label(
+    target: &str,
+    name: Option<&str>,
+    notes: Option<&str>,
+    tags: &[String],
+    untags: &[String],
+) -> Result<()> {
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+
+    if name.is_none() && notes.is_none() && tags.is_empty() && untags.is_empty() {
+        print_labels(&config, &url);
+        return Ok(());
+    }
+
+    // 先验证名称，避免写入无效配置
+    let name = match name.map(str::trim) {
+        Some("") => Some(None),
+        Some(name) => {
+            validate_subscription_name(&config, &url, name)?;
+            Some(Some(name.to_string()))
+        }
+        None => None,
+    };
+
+    let record = config.subscription_record_mut(&url)?;
+    if let Some(notes) = notes.map(str::trim) {
+        record.notes = Some(notes.to_string()).filter(|notes| !notes.is_empty());
+    }
+    let options = &mut record.options;
+    if let Some(name) = name {
+        options.name = name;
+    }
+    add_tags(&mut options.tags, tags);
+    options.tags.retain(|tag| !untags.contains(tag));
+    config.save()?;
+
+    println!("{}", t!("command.hosts.label.success", url = url));
+    print_labels(&config, &url);
+    Ok(())
+}
+
+/// 显示订阅当前的名称、备注和标签
+fn print_labels(config: &Config, url: &str) {
+    let Some(record) = config.get_subscription_record(url) else {
+        return;
+    };
+    let options = &record.options;
+    let none = t!("command.hosts.label.none");
+
+    println!(
+        "{}",
+        t!(
+            "command.hosts.label.current_name",
+            value = options.name.as_deref().unwrap_or(&none)
+        )
+    );
+    let tags = if options.tags.is_empty() {
+        none.to_string()
+    } else {
+        options.tags.join(", ")
+    };
+    println!("{}", t!("command.hosts.label.current_tags", value = tags));
+    println!(
+        "{}",
+        t!(
+            "command.hosts.label.current_notes",
+            value = record.notes.as_deref().unwrap_or(&none)
+        )
+    );
+}
@@ -1,5 +1,9 @@
 use crate::commands::config::Config;
-use crate::commands::hosts::{core::HostsFileStructure, create_hosts_manager};
+use crate::commands::hosts::{
+    core::HostsFileStructure,
+    create_hosts_manager,
+    helpers::{format_duration, get_current_timestamp},
+};
 use crate::core::i18n::t;
 use crate::core::table::{add_table_row, create_subscription_table, print_table, set_table_header};
 use anyhow::Result;
@@ -34,14 +38,32 @@ pub fn handle_list() -> Result<()> {
         &mut table,
         vec![
             t!("command.hosts.list.table_header_index").to_string(),
+            t!("command.hosts.list.table_header_name").to_string(),
             t!("command.hosts.list.table_header_url").to_string(),
+            t!("command.hosts.list.table_header_last_updated").to_string(),
+            t!("command.hosts.list.table_header_entries").to_string(),
             t!("command.hosts.list.table_header_status").to_string(),
         ],
     );
 
     // 显示每个订阅
-    for (index, url) in subscriptions.iter().enumerate() {
-        let status = if hosts_subscriptions.contains(url) {
+    let now = get_current_timestamp();
+    let mut failed = Vec::new();
+    for (index, record) in config.hosts_subscriptions.iter().enumerate() {
+        let (url, options) = (&record.url, &record.options);
+        let status = if options.disabled {
+            match options.disabled_until {
+                Some(until) => t!(
+                    "command.hosts.list.status_snoozed",
+                    remaining = format_duration(until.saturating_sub(now))
+                )
+                .to_string(),
+                None => t!("command.hosts.list.status_disabled").to_string(),
+            }
+        } else if let Some(error) = &record.last_error {
+            failed.push((index, url, error));
+            t!("command.hosts.list.status_failed").to_string()
+        } else if hosts_subscriptions.contains(url) {
             t!("command.hosts.list.status_applied").to_string()
         } else {
             t!("command.hosts.list.status_not_synced").to_string()
@@ -51,20 +73,59 @@ pub fn handle_list() -> Result<()> {
         } else {
             url.clone()
         };
+        let mut label = options.name.clone().unwrap_or_default();
+        if !options.tags.is_empty() {
+            label = format!("{label} [{}]", options.tags.join(", "))
+                .trim()
+                .to_string();
+        }
+        let last_updated = match record.last_updated {
+            Some(timestamp) => t!(
+                "command.hosts.cache.fetched_ago",
+                duration = format_duration(now.saturating_sub(timestamp))
+            )
+            .to_string(),
+            None => "-".to_string(),
+        };
+        let entries = record
+            .entry_count
+            .map(|count| count.to_string())
+            .unwrap_or_else(|| "-".to_string());
         add_table_row(
             &mut table,
-            vec![(index + 1).to_string(), display_url, status],
+            vec![
+                (index + 1).to_string(),
+                label,
+                display_url,
+                last_updated,
+                entries,
+                status,
+            ],
         );
     }
     print_table(&table);
 
+    // 显示上次更新失败的原因
+    if !failed.is_empty() {
+        println!("{}", t!("command.hosts.list.failed_list"));
+        for (index, url, error) in &failed {
+            println!("   {}. {url}", index + 1);
+            println!("{}", t!("command.hosts.list.failed_error", error = error));
+        }
+        println!();
+    }
+
     // 显示统计信息
     let total_count = subscriptions.len();
+    let disabled_count = subscriptions
+        .iter()
+        .filter(|url| config.is_subscription_disabled(url))
+        .count();
     let active_count = subscriptions
         .iter()
-        .filter(|url| hosts_subscriptions.contains(url))
+        .filter(|url| !config.is_subscription_disabled(url) && hosts_subscriptions.contains(url))
         .count();
-    let inactive_count = total_count - active_count;
+    let inactive_count = total_count - active_count - disabled_count;
 
     println!("{}", t!("command.hosts.list.statistics_title"));
     println!(
@@ -75,6 +136,12 @@ pub fn handle_list() -> Result<()> {
         "{}",
         t!("command.hosts.list.applied_count", count = active_count)
     );
+    if disabled_count > 0 {
+        println!(
+            "{}",
+            t!("command.hosts.list.disabled_count", count = disabled_count)
+        );
+    }
     if inactive_count > 0 {
         println!(
             "{}",
@@ -92,7 +159,7 @@ pub fn handle_list() -> Result<()> {
         println!();
         println!("{}", t!("command.hosts.list.not_synced_list"));
         for url in &subscriptions {
-            if !hosts_subscriptions.contains(url) {
+            if !config.is_subscription_disabled(url) && !hosts_subscriptions.contains(url) {
                 println!("   • {url}");
             }
         }
@@ -107,3 +174,52 @@ pub fn get_all_subscriptions() -> Result<Vec<String>> {
     let structure: HostsFileStructure = hosts_manager.parse_file()?;
     Ok(structure.get_all_subscriptions())
 }
+
+/// 处理搜索命令：按主机名查找 hosts 文件中的条目
+pub fn handle_search(pattern: &str) -> Result<()> {
+    println!("{}", t!("command.hosts.search.title", pattern = pattern));
+    println!();
+
+    let hosts_manager = create_hosts_manager()?;
+    let structure: HostsFileStructure = hosts_manager.parse_file()?;
+    let entries = structure.find_entries(pattern);
+
+    if entries.is_empty() {
+        println!("{}", t!("command.hosts.search.empty"));
+        return Ok(());
+    }
+
+    let mut table = create_subscription_table();
+    set_table_header(
+        &mut table,
+        vec![
+            t!("command.hosts.list.table_header_index").to_string(),
+            t!("command.hosts.search.table_header_ip").to_string(),
+            t!("command.hosts.search.table_header_hostnames").to_string(),
+            t!("command.hosts.search.table_header_source").to_string(),
+        ],
+    );
+    for (index, entry) in entries.iter().enumerate() {
+        let mut hostnames = entry.hostnames().collect::<Vec<_>>().join(" ");
+        if let Some(comment) = &entry.comment {
+            hostnames.push_str(&format!(" # {comment}"));
+        }
+        add_table_row(
+            &mut table,
+            vec![
+                (index + 1).to_string(),
+                entry.ip.to_string(),
+                hostnames,
+                entry.source.to_string(),
+            ],
+        );
+    }
+    print_table(&table);
+
+    println!(
+        "{}",
+        t!("command.hosts.search.statistics", count = entries.len())
+    );
+
+    Ok(())
+}
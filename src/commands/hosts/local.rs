@@ -0,0 +1,124 @@
+use crate::commands::hosts::{
+    core::HostsFileStructure,
+    entry::{EntrySource, HostEntry},
+    helpers::{create_hosts_manager, ensure_hosts_write_permission, save_hosts_structure},
+    toggle::reenable_expired_subscriptions,
+};
+use crate::core::i18n::t;
+use crate::core::table::{add_table_row, create_subscription_table, print_table, set_table_header};
+use crate::core::validation::validate_hostname;
+use anyhow::Result;
+use std::net::IpAddr;
+
+/// 处理添加本地条目命令
+pub fn handle_add(ip: &str, hostnames: &[String], comment: Option<&str>) -> Result<()> {
+    // 参数验证
+    let ip: IpAddr = ip
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{}", t!("error.hosts_invalid_ip", ip = ip)))?;
+    let (canonical, aliases) = hostnames
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("{}", t!("error.hosts_missing_hostname")))?;
+    for hostname in hostnames {
+        validate_hostname(hostname)?;
+    }
+
+    // 权限检查
+    ensure_hosts_write_permission()?;
+    reenable_expired_subscriptions();
+
+    let entry = HostEntry {
+        ip,
+        canonical: canonical.clone(),
+        aliases: aliases.to_vec(),
+        comment: comment
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty()),
+        source: EntrySource::Local,
+    };
+    let line = entry.to_line();
+
+    let hosts_manager = create_hosts_manager()?;
+    let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+    let replaced = structure.add_local_entry(entry);
+    save_hosts_structure(&hosts_manager, &mut structure)?;
+
+    println!("{}", t!("command.hosts.add.success", entry = line));
+    if replaced > 0 {
+        println!("{}", t!("command.hosts.add.replaced", count = replaced));
+    }
+
+    Ok(())
+}
+
+/// 处理移除本地条目命令
+pub fn handle_remove(hostname: &str) -> Result<()> {
+    // 权限检查
+    ensure_hosts_write_permission()?;
+    reenable_expired_subscriptions();
+
+    let hosts_manager = create_hosts_manager()?;
+    let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+
+    if !structure.remove_local_hostname(hostname) {
+        println!(
+            "{}",
+            t!("command.hosts.remove.not_found", hostname = hostname)
+        );
+        return Ok(());
+    }
+
+    save_hosts_structure(&hosts_manager, &mut structure)?;
+    println!(
+        "{}",
+        t!("command.hosts.remove.success", hostname = hostname)
+    );
+
+    Ok(())
+}
+
+/// 处理本地条目列表命令
+pub fn handle_entries() -> Result<()> {
+    println!("{}", t!("command.hosts.entries.title"));
+    println!();
+
+    let hosts_manager = create_hosts_manager()?;
+    let structure: HostsFileStructure = hosts_manager.parse_file()?;
+    let entries = structure.local_entries();
+
+    if entries.is_empty() {
+        println!("{}", t!("command.hosts.entries.empty"));
+        println!("{}", t!("command.hosts.entries.empty_hint"));
+        return Ok(());
+    }
+
+    let mut table = create_subscription_table();
+    set_table_header(
+        &mut table,
+        vec![
+            t!("command.hosts.list.table_header_index").to_string(),
+            t!("command.hosts.search.table_header_ip").to_string(),
+            t!("command.hosts.search.table_header_hostnames").to_string(),
+            t!("command.hosts.entries.table_header_comment").to_string(),
+        ],
+    );
+    for (index, entry) in entries.iter().enumerate() {
+        add_table_row(
+            &mut table,
+            vec![
+                (index + 1).to_string(),
+                entry.ip.to_string(),
+                entry.hostnames().collect::<Vec<_>>().join(" "),
+                entry.comment.clone().unwrap_or_default(),
+            ],
+        );
+    }
+    print_table(&table);
+
+    println!(
+        "{}",
+        t!("command.hosts.entries.statistics", count = entries.len())
+    );
+
+    Ok(())
+}
@@ -1,12 +1,38 @@
 pub mod backup;
+pub mod cache;
+pub mod conflicts;
 pub mod core;
+pub mod diff;
+pub mod entry;
+pub mod export;
+pub mod filter;
+pub mod format;
 pub mod helpers;
+pub mod integrity;
+pub mod label;
 pub mod list;
+pub mod local;
 pub mod operations;
+pub mod priority;
+pub mod protect;
+pub mod rewrite;
+pub mod rules;
+pub mod toggle;
 pub mod validation;
 
 pub use backup::{handle_backup, handle_restore};
+pub use cache::{handle_cache_clear, handle_cache_list};
+pub use conflicts::handle_conflicts;
 pub use core::{execute, register_command};
+pub use diff::handle_diff;
+pub use export::handle_export;
+pub use filter::{handle_filter_add, handle_filter_list, handle_filter_remove};
 pub use helpers::create_hosts_manager;
-pub use list::handle_list;
+pub use integrity::{handle_pin, handle_public_key};
+pub use label::handle_label;
+pub use list::{handle_list, handle_search};
+pub use local::{handle_add, handle_entries, handle_remove};
 pub use operations::{handle_subscribe, handle_unsubscribe, handle_update};
+pub use priority::{handle_move, handle_priority};
+pub use rewrite::handle_rewrite;
+pub use toggle::{handle_disable, handle_enable};
@@ -1,24 +1,53 @@
-use crate::commands::config::Config;
+use crate::commands::config::{Config, model::SubscriptionOptions};
 use crate::commands::hosts::{
+    cache,
+    conflicts::{current_conflicts, find_conflicts, warn_new_conflicts},
     core::HostsFileStructure,
+    entry::HostEntry,
+    format::ListFormat,
     helpers::{
-        create_hosts_manager, display_update_summary, generate_backup_filename,
-        print_content_preview,
+        create_hosts_manager, display_update_summary, ensure_hosts_write_permission,
+        preview_hosts_structure, print_content_preview, require_subscription_target,
+        save_hosts_structure,
     },
-    validation::validate_hosts_content,
+    integrity::{Integrity, PublicKey, validate_sha256},
+    label::{add_tags, validate_subscription_name},
+    rules::SubscriptionRules,
+    toggle::reenable_expired_subscriptions,
+    validation::{ValidationLevel, ValidationPolicy, validate_hosts_content},
 };
-use crate::core::http::HttpClient;
+use crate::core::file_source::{fetch_file, file_source_path, normalize_source};
+use crate::core::http::{Attempted, CacheValidators, FetchResult, HttpClient};
 use crate::core::i18n::t;
-use crate::core::permission::ensure_sudo_privileges;
 use crate::core::validation::validate_url;
 use anyhow::Result;
-
-/// 处理订阅命令
-pub fn handle_subscribe(url: &str) -> Result<()> {
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// 处理订阅命令，`dry_run` 时只显示 hosts 文件将发生的变化
+///
+/// `name` 和 `tags` 会记录到订阅选项中，可用于之后按名称或标签选择订阅。
+/// 指定 `format` 时按该格式转换内容，否则沿用订阅已有的格式设置（默认自动检测）。
+/// 指定 `sha256` 或 `public_key` 时下载的内容必须通过校验，校验设置同样记录到订阅选项中。
+pub fn handle_subscribe(
+    url: &str,
+    name: Option<&str>,
+    tags: &[String],
+    format: Option<ListFormat>,
+    sha256: Option<&str>,
+    public_key: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    // 本地路径转换为 file:// URL
+    let url = &normalize_source(url)?;
     println!("{}", t!("command.hosts.subscribe.starting", url = url));
 
-    // 权限检查
-    ensure_sudo_privileges()?;
+    // 权限检查（预览不写入文件，不需要权限）
+    if !dry_run {
+        ensure_hosts_write_permission()?;
+        reenable_expired_subscriptions();
+    }
 
     // URL 验证
     validate_url(url)?;
@@ -27,37 +56,104 @@ pub fn handle_subscribe(url: &str) -> Result<()> {
     let mut config = Config::load()?;
     let current_subscriptions = config.get_hosts_subscriptions();
     let url_string = url.to_string();
-    if current_subscriptions.contains(&url_string) {
+    let is_new = !current_subscriptions.contains(&url_string);
+    if !is_new {
         println!(
             "{}",
             t!("command.hosts.subscribe.already_exists", url = url)
         );
     }
 
+    if let Some(name) = name {
+        validate_subscription_name(&config, url, name)?;
+    }
+
+    // 新订阅先加入配置（订阅失败时不会保存），之后的选项都记录在该订阅上
+    config.add_hosts_subscription(url)?;
+
+    // 命令行指定的校验设置优先于已有的订阅选项
+    let mut integrity_options = config.get_subscription_options(url);
+    if let Some(sha256) = sha256 {
+        integrity_options.sha256 = Some(validate_sha256(sha256)?);
+    }
+    if let Some(public_key) = public_key {
+        PublicKey::parse(public_key)?;
+        integrity_options.public_key = Some(public_key.trim().to_string());
+    }
+    let integrity = Integrity::from_options(&integrity_options, false)?;
+
+    // 格式设置在处理内容前生效
+    if let Some(format) = format {
+        config.subscription_options_mut(url)?.format = format;
+    }
+    let format = config.get_subscription_options(url).format;
+
+    // 重新订阅视为重新启用
+    if config.is_subscription_disabled(url) {
+        let options = config.subscription_options_mut(url)?;
+        options.disabled = false;
+        options.disabled_until = None;
+        println!("{}", t!("command.hosts.subscribe.reenabled", url = url));
+    }
+
     // 下载并验证内容
     println!("{}", t!("command.hosts.subscribe.downloading"));
-    let content = download_and_validate_hosts(url)?;
+    let http_client = HttpClient::new(&config.http.client_options()?)?;
+    let policy = ValidationPolicy::from_config(&config);
+    let (content, raw, validators) =
+        download_hosts(&http_client, url, &integrity, format, &policy)?;
 
     // 显示内容预览
-    print_content_preview(&content);
+    print_content_preview(&content, format);
+
+    if dry_run {
+        let hosts_manager = create_hosts_manager()?;
+        let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+        let conflicts_before = find_conflicts(&structure);
+        let entries = process_subscription(&config, url, &content)?;
+        structure.add_or_update_subscription(url, &entries);
+        warn_new_conflicts(&conflicts_before, &find_conflicts(&structure));
+        return preview_hosts_structure(&hosts_manager, &mut structure);
+    }
 
     // 更新 hosts 文件（会自动备份）
     println!("{}", t!("command.hosts.subscribe.updating_hosts"));
+    let conflicts_before = current_conflicts();
 
     // 尝试更新 hosts 文件
-    if let Err(hosts_err) = add_or_update_subscription(url, &content) {
-        println!(
-            "{}",
-            t!("command.hosts.hosts_file_update_failed", error = hosts_err)
-        );
-        return Err(hosts_err);
+    let entry_count = match add_or_update_subscription(&config, url, &content) {
+        Ok(entry_count) => entry_count,
+        Err(hosts_err) => {
+            println!(
+                "{}",
+                t!("command.hosts.hosts_file_update_failed", error = hosts_err)
+            );
+            return Err(hosts_err);
+        }
+    };
+
+    // 缓存内容并记录缓存验证信息，供离线重建和后续条件更新使用
+    cache::store_or_warn(url, &raw, &content, format);
+    let record = config.subscription_record_mut(url)?;
+    record.mark_updated(entry_count);
+    let options = &mut record.options;
+    options.etag = validators.etag;
+    options.last_modified = validators.last_modified;
+    if let Some(name) = name {
+        options.name = Some(name.to_string());
     }
+    add_tags(&mut options.tags, tags);
+    options.sha256 = integrity_options.sha256;
+    options.public_key = integrity_options.public_key;
 
     // hosts 文件更新成功，继续更新配置
     println!("{}", t!("command.hosts.subscribe.updating_config"));
 
     // 尝试更新配置
-    update_config_and_handle_rollback(&mut config, url)?;
+    update_config_and_handle_rollback(&mut config, url, is_new)?;
+
+    // 提示新增的主机名冲突
+    warn_new_conflicts(&conflicts_before, &current_conflicts());
 
     // 显示订阅统计
     let subscriptions = config.get_hosts_subscriptions();
@@ -72,12 +168,12 @@ pub fn handle_subscribe(url: &str) -> Result<()> {
     Ok(())
 }
 
-/// 更新配置并处理可能的回滚
-fn update_config_and_handle_rollback(config: &mut Config, url: &str) -> Result<()> {
+/// 更新配置并处理可能的回滚，`is_new` 表示订阅前配置中没有该订阅
+fn update_config_and_handle_rollback(config: &mut Config, url: &str, is_new: bool) -> Result<()> {
     match config.add_hosts_subscription(url) {
-        Ok(added) => match config.save() {
+        Ok(_) => match config.save() {
             Ok(()) => {
-                if added {
+                if is_new {
                     println!("{}", t!("command.hosts.subscribe.success", url = url));
                 } else {
                     println!("{}", t!("command.hosts.subscribe.updated", url = url));
@@ -133,12 +229,16 @@ fn handle_config_update_failure(config_err: anyhow::Error) -> Result<()> {
     Err(config_err)
 }
 
-/// 处理取消订阅命令
-pub fn handle_unsubscribe(url: &str) -> Result<()> {
+/// 处理取消订阅命令，`dry_run` 时只显示 hosts 文件将发生的变化
+pub fn handle_unsubscribe(url: &str, dry_run: bool) -> Result<()> {
+    let url = &normalize_source(url)?;
     println!("{}", t!("command.hosts.unsubscribe.starting", url = url));
 
-    // 权限检查
-    ensure_sudo_privileges()?;
+    // 权限检查（预览不写入文件，不需要权限）
+    if !dry_run {
+        ensure_hosts_write_permission()?;
+        reenable_expired_subscriptions();
+    }
 
     // 检查配置文件中是否存在该订阅
     let mut config = Config::load()?;
@@ -158,6 +258,18 @@ pub fn handle_unsubscribe(url: &str) -> Result<()> {
         return Ok(());
     }
 
+    if dry_run {
+        let hosts_manager = create_hosts_manager()?;
+        let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+        if !structure.remove_subscription(url) {
+            println!(
+                "{}",
+                t!("command.hosts.subscription_not_found_hosts", url = url)
+            );
+        }
+        return preview_hosts_structure(&hosts_manager, &mut structure);
+    }
+
     // 从 hosts 文件中移除订阅块
     println!("{}", t!("command.hosts.unsubscribe.removing_from_hosts"));
     let removed_from_hosts = remove_subscription(url)?;
@@ -166,6 +278,9 @@ pub fn handle_unsubscribe(url: &str) -> Result<()> {
     println!("{}", t!("command.hosts.unsubscribe.updating_config"));
     let removed_from_config = config.remove_hosts_subscription(url)?;
     config.save()?;
+    if let Err(e) = cache::remove(url) {
+        println!("{}", t!("command.hosts.cache.remove_failed", error = e));
+    }
 
     // 显示结果
     if removed_from_hosts && removed_from_config {
@@ -200,19 +315,50 @@ pub fn handle_unsubscribe(url: &str) -> Result<()> {
 }
 
 /// 处理更新命令
-pub fn handle_update() -> Result<()> {
+///
+/// `targets`（URL、序号或名称）和 `tags` 都未指定时更新所有订阅，否则只更新选中的订阅。
+/// `dry_run` 时下载所有订阅但只显示 hosts 文件将发生的变化；
+/// `offline` 时不访问网络，使用缓存内容重建所有订阅块；
+/// `accept_new` 时接受与固定 SHA-256 不一致的新内容，并将固定值更新为新内容的校验和。
+pub fn handle_update(
+    targets: &[String],
+    tags: &[String],
+    dry_run: bool,
+    offline: bool,
+    accept_new: bool,
+) -> Result<()> {
     println!("{}", t!("command.hosts.update.starting"));
 
-    // 权限检查
-    ensure_sudo_privileges()?;
+    // 权限检查（预览不写入文件，不需要权限）
+    if !dry_run {
+        ensure_hosts_write_permission()?;
+        reenable_expired_subscriptions();
+    }
 
-    // 获取所有订阅
-    let config = Config::load()?;
-    let subscriptions = config.get_hosts_subscriptions();
+    // 获取所有订阅，已禁用的订阅不更新
+    let mut config = Config::load()?;
+    let (disabled, subscriptions): (Vec<String>, Vec<String>) =
+        select_subscriptions(&config, targets, tags)?
+            .into_iter()
+            .partition(|url| config.is_subscription_disabled(url));
+
+    if !disabled.is_empty() {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.update.skipped_disabled",
+                count = disabled.len()
+            )
+        );
+    }
 
     if subscriptions.is_empty() {
-        println!("{}", t!("command.hosts.update.empty"));
-        println!("{}", t!("command.hosts.update.empty_hint"));
+        if targets.is_empty() && tags.is_empty() {
+            println!("{}", t!("command.hosts.update.empty"));
+            println!("{}", t!("command.hosts.update.empty_hint"));
+        } else {
+            println!("{}", t!("command.hosts.update.no_match"));
+        }
         return Ok(());
     }
 
@@ -225,11 +371,32 @@ pub fn handle_update() -> Result<()> {
     );
     println!();
 
+    // 并发下载所有订阅，然后按优先级顺序统一应用到同一个结构
+    let concurrency = config.update_concurrency();
+    if !offline {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.update.downloading",
+                count = subscriptions.len(),
+                concurrency = concurrency
+            )
+        );
+        println!();
+    }
+    let fetched = fetch_subscriptions(&config, &subscriptions, offline, accept_new, concurrency)?;
+    if !offline {
+        println!();
+    }
+
+    let hosts_manager = create_hosts_manager()?;
+    let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+    let conflicts_before = find_conflicts(&structure);
     let mut success_count = 0;
+    let mut unchanged_count = 0;
     let mut failed_urls = Vec::with_capacity(subscriptions.len());
 
-    // 逐个更新订阅
-    for (index, url) in subscriptions.iter().enumerate() {
+    for (index, (url, result)) in subscriptions.iter().zip(fetched).enumerate() {
         println!(
             "{}",
             t!(
@@ -240,8 +407,28 @@ pub fn handle_update() -> Result<()> {
             )
         );
 
-        match update_single_subscription(url) {
-            Ok(()) => {
+        let result = result.and_then(|fetched| {
+            if fetched.attempts > 1 {
+                println!(
+                    "{}",
+                    t!("command.hosts.update.attempts", count = fetched.attempts)
+                );
+            }
+            apply_subscription(&mut structure, &mut config, url, fetched, !dry_run)
+        });
+        match result {
+            Ok(UpdateOutcome::Unchanged) => {
+                unchanged_count += 1;
+                println!(
+                    "{}",
+                    t!(
+                        "command.hosts.update.unchanged_item",
+                        current = index + 1,
+                        total = subscriptions.len()
+                    )
+                );
+            }
+            Ok(UpdateOutcome::Updated) => {
                 success_count += 1;
                 println!(
                     "{}",
@@ -254,6 +441,11 @@ pub fn handle_update() -> Result<()> {
             }
             Err(e) => {
                 failed_urls.push(url.clone());
+                if !dry_run {
+                    config
+                        .subscription_record_mut(url)?
+                        .mark_failed(&e.to_string());
+                }
                 println!(
                     "{}",
                     t!(
@@ -266,49 +458,177 @@ pub fn handle_update() -> Result<()> {
             }
         }
 
-        // 添加短暂延迟，避免过于频繁的请求
-        if index < subscriptions.len() - 1 {
-            std::thread::sleep(std::time::Duration::from_millis(500));
-        }
+        println!();
+    }
+
+    // 提示本次更新新增的主机名冲突
+    warn_new_conflicts(&conflicts_before, &find_conflicts(&structure));
 
+    if dry_run {
+        display_update_summary(
+            success_count,
+            unchanged_count,
+            &failed_urls,
+            subscriptions.len(),
+        );
         println!();
+        return preview_hosts_structure(&hosts_manager, &mut structure);
     }
 
+    // 所有变化只备份并写入一次
+    if success_count > 0 {
+        save_hosts_structure(&hosts_manager, &mut structure)?;
+        println!("{}", t!("command.hosts.hosts_file_updated"));
+        println!();
+    }
+    config.save()?;
+
     // 显示更新结果摘要
-    display_update_summary(success_count, &failed_urls, subscriptions.len());
+    display_update_summary(
+        success_count,
+        unchanged_count,
+        &failed_urls,
+        subscriptions.len(),
+    );
 
     Ok(())
 }
 
-/// 下载并验证 hosts 列表
-fn download_and_validate_hosts(url: &str) -> Result<String> {
-    let http_client = HttpClient::new()?;
-
-    // 先测试 URL 可达性
-    if !http_client.test_url(url)? {
-        anyhow::bail!(t!("error.hosts_url_not_accessible", url = url));
+/// 选择要更新的订阅，结果保持配置中的优先级顺序
+///
+/// 选中通过 `targets` 指定的订阅和带有任一 `tags` 标签的订阅；都未指定时选择所有订阅。
+fn select_subscriptions(
+    config: &Config,
+    targets: &[String],
+    tags: &[String],
+) -> Result<Vec<String>> {
+    let subscriptions = config.get_hosts_subscriptions();
+    if targets.is_empty() && tags.is_empty() {
+        return Ok(subscriptions);
     }
 
-    // 下载内容
-    let content = http_client.download(url)?;
+    let selected = targets
+        .iter()
+        .map(|target| require_subscription_target(config, target))
+        .collect::<Result<Vec<String>>>()?;
+
+    Ok(subscriptions
+        .into_iter()
+        .filter(|url| {
+            selected.contains(url)
+                || config
+                    .get_subscription_options(url)
+                    .tags
+                    .iter()
+                    .any(|tag| tags.contains(tag))
+        })
+        .collect())
+}
 
-    // 验证内容格式
-    validate_hosts_content(&content)?;
+/// 单个订阅的更新结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// 内容有变化，已重写订阅块
+    Updated,
+    /// 内容未变化，跳过重写和备份
+    Unchanged,
+}
+
+/// 下载并验证 hosts 列表（提供缓存验证信息时发送条件请求）
+///
+/// `file://` 订阅直接读取本地文件，按修改时间和内容哈希检测变化。
+/// 新下载的内容先通过 `integrity` 的校验和与签名校验，再按 `format` 和 `policy` 验证内容。
+fn download_and_validate_hosts(
+    http_client: &HttpClient,
+    url: &str,
+    validators: &CacheValidators,
+    integrity: &Integrity,
+    format: ListFormat,
+    policy: &ValidationPolicy,
+) -> Result<Attempted<FetchResult>> {
+    let result = match file_source_path(url) {
+        Some(path) => Attempted {
+            value: fetch_file(&path, validators, http_client.max_size())?,
+            attempts: 1,
+        },
+        // 直接发送（条件）请求，内容未变化时只需一次 304 往返
+        None => http_client.fetch(url, validators)?,
+    };
+
+    // 校验内容完整性并验证内容格式
+    if let FetchResult::Modified { content, raw, .. } = &result.value {
+        integrity.verify(http_client, url, raw)?;
+        validate_hosts_content(url, content, format, policy)?;
+    }
 
-    Ok(content)
+    Ok(result)
+}
+
+/// 完整下载并验证 hosts 列表，返回解码后的内容、原始字节及其缓存验证信息
+fn download_hosts(
+    http_client: &HttpClient,
+    url: &str,
+    integrity: &Integrity,
+    format: ListFormat,
+    policy: &ValidationPolicy,
+) -> Result<(String, Vec<u8>, CacheValidators)> {
+    let validators = CacheValidators::default();
+    let fetched =
+        download_and_validate_hosts(http_client, url, &validators, integrity, format, policy)?;
+    if fetched.attempts > 1 {
+        println!(
+            "{}",
+            t!("command.hosts.update.attempts", count = fetched.attempts)
+        );
+    }
+    match fetched.value {
+        FetchResult::Modified {
+            content,
+            raw,
+            validators,
+        } => Ok((content, raw, validators)),
+        // 未发送条件请求时服务器不应返回 304
+        FetchResult::NotModified { .. } => anyhow::bail!(
+            "{}",
+            t!(
+                "error.hosts_download_http_error",
+                status = "304 Not Modified"
+            )
+        ),
+    }
 }
 
-/// 添加或更新订阅
-pub fn add_or_update_subscription(url: &str, content: &str) -> Result<()> {
+/// 添加或更新订阅，返回写入订阅块的条目数
+pub fn add_or_update_subscription(config: &Config, url: &str, content: &str) -> Result<usize> {
     let hosts_manager = create_hosts_manager()?;
     let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
 
-    structure.add_or_update_subscription(url, content);
-    let backup_filename = generate_backup_filename();
-    hosts_manager.update_structure_with_backup(&structure, &backup_filename)?;
+    let entries = process_subscription(config, url, content)?;
+    structure.add_or_update_subscription(url, &entries);
+    save_hosts_structure(&hosts_manager, &mut structure)?;
 
     println!("{}", t!("command.hosts.hosts_file_updated"));
-    Ok(())
+    Ok(entries.len())
+}
+
+/// 按订阅格式转换下载的内容并应用该订阅的过滤和重写规则
+///
+/// 受保护的主机名会被移除并给出警告；严格验证模式下包含受保护主机名的订阅处理失败。
+pub fn process_subscription(config: &Config, url: &str, content: &str) -> Result<Vec<HostEntry>> {
+    let rules = SubscriptionRules::from_config(config, url)?;
+    let (entries, report) = rules.process(url, content);
+    report.print();
+    if config.hosts_validation == ValidationLevel::Strict && !report.protected.is_empty() {
+        anyhow::bail!(
+            "{}",
+            t!(
+                "error.hosts_protected_strict",
+                count = report.protected.dropped,
+                hostnames = report.protected.hostname_list()
+            )
+        );
+    }
+    Ok(entries)
 }
 
 /// 移除订阅
@@ -318,8 +638,7 @@ pub fn remove_subscription(url: &str) -> Result<bool> {
 
     let removed = structure.remove_subscription(url);
     if removed {
-        let backup_filename = generate_backup_filename();
-        hosts_manager.update_structure_with_backup(&structure, &backup_filename)?;
+        save_hosts_structure(&hosts_manager, &mut structure)?;
         println!("{}", t!("command.hosts.subscription_removed", url = url));
     } else {
         println!(
@@ -331,16 +650,164 @@ pub fn remove_subscription(url: &str) -> Result<bool> {
     Ok(removed)
 }
 
-/// 更新单个订阅
-pub fn update_single_subscription(url: &str) -> Result<()> {
+/// 下载阶段得到的订阅内容
+struct FetchedSubscription {
+    /// 订阅内容（服务器返回 304 或离线时为缓存内容）
+    content: String,
+    /// 需要记录的缓存验证信息，使用缓存内容且验证信息未变化时为 None
+    validators: Option<CacheValidators>,
+    /// 新下载内容的原始字节（需要写入缓存），使用缓存内容时为 None
+    raw: Option<Vec<u8>>,
+    /// 下载订阅内容发送的请求总数（包括所有重试），离线时为 0
+    attempts: u32,
+    /// 接受新内容后需要记录的 SHA-256 固定值
+    new_pin: Option<String>,
+}
+
+/// 使用最多 `concurrency` 个线程下载所有订阅，结果与 `urls` 顺序一致
+fn fetch_subscriptions(
+    config: &Config,
+    urls: &[String],
+    offline: bool,
+    accept_new: bool,
+    concurrency: usize,
+) -> Result<Vec<Result<FetchedSubscription>>> {
+    let http_client = HttpClient::new(&config.http.client_options()?)?;
+    let policy = ValidationPolicy::from_config(config);
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, urls.len().max(1)) {
+            let (next, sender, http_client, policy) =
+                (&next, sender.clone(), &http_client, &policy);
+            scope.spawn(move || {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(url) = urls.get(index) else {
+                        break;
+                    };
+                    let options = config.get_subscription_options(url);
+                    let result =
+                        fetch_subscription(http_client, url, &options, policy, offline, accept_new);
+                    let _ = sender.send((index, result));
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<Option<Result<FetchedSubscription>>> = urls.iter().map(|_| None).collect();
+    for (index, result) in receiver {
+        results[index] = Some(result);
+    }
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// 获取单个订阅的内容
+///
+/// 有缓存时发送条件请求，服务器返回 304 时使用缓存内容；
+/// `offline` 时不访问网络，直接使用缓存内容。新下载的内容按订阅选项校验完整性，并按 `policy` 验证内容。
+fn fetch_subscription(
+    http_client: &HttpClient,
+    url: &str,
+    options: &SubscriptionOptions,
+    policy: &ValidationPolicy,
+    offline: bool,
+    accept_new: bool,
+) -> Result<FetchedSubscription> {
     // URL 验证
     validate_url(url)?;
 
-    // 下载内容
-    let content = download_and_validate_hosts(url)?;
+    if offline {
+        return Ok(FetchedSubscription {
+            content: cache::require_normalized(url)?,
+            validators: None,
+            raw: None,
+            attempts: 0,
+            new_pin: None,
+        });
+    }
 
-    // 更新 hosts 文件
-    add_or_update_subscription(url, &content)?;
+    // 没有缓存时 304 无法提供内容，需要完整下载
+    let cached = cache::load_normalized(url)?;
+    let validators = if cached.is_some() {
+        CacheValidators {
+            etag: options.etag.clone(),
+            last_modified: options.last_modified.clone(),
+        }
+    } else {
+        CacheValidators::default()
+    };
+
+    let integrity = Integrity::from_options(options, accept_new)?;
+    let fetched = download_and_validate_hosts(
+        http_client,
+        url,
+        &validators,
+        &integrity,
+        options.format,
+        policy,
+    )?;
+    match fetched.value {
+        FetchResult::NotModified { validators } => Ok(FetchedSubscription {
+            content: cached
+                .ok_or_else(|| anyhow::anyhow!("{}", t!("error.hosts_cache_missing", url = url)))?,
+            validators,
+            raw: None,
+            attempts: fetched.attempts,
+            new_pin: None,
+        }),
+        FetchResult::Modified {
+            content,
+            raw,
+            validators,
+        } => Ok(FetchedSubscription {
+            new_pin: integrity.new_pin(&raw),
+            content,
+            validators: Some(validators),
+            raw: Some(raw),
+            attempts: fetched.attempts,
+        }),
+    }
+}
 
-    Ok(())
+/// 将下载的订阅内容应用到 hosts 结构
+///
+/// 处理后的条目与现有订阅块相同时不做修改。`persist` 时缓存新下载的内容，
+/// 并将缓存验证信息和更新状态记录到配置中（不保存配置文件）；离线重建不算作一次更新。
+fn apply_subscription(
+    structure: &mut HostsFileStructure,
+    config: &mut Config,
+    url: &str,
+    fetched: FetchedSubscription,
+    persist: bool,
+) -> Result<UpdateOutcome> {
+    let entries = process_subscription(config, url, &fetched.content)?;
+
+    if persist && fetched.attempts > 0 {
+        config
+            .subscription_record_mut(url)?
+            .mark_updated(entries.len());
+    }
+    if persist && let Some(raw) = &fetched.raw {
+        let format = config.get_subscription_options(url).format;
+        cache::store_or_warn(url, raw, &fetched.content, format);
+    }
+    if persist && let Some(validators) = fetched.validators {
+        let options = config.subscription_options_mut(url)?;
+        options.etag = validators.etag;
+        options.last_modified = validators.last_modified;
+        if let Some(pin) = fetched.new_pin {
+            options.sha256 = Some(pin);
+        }
+    }
+
+    if structure.subscription_entries(url).as_ref() == Some(&entries) {
+        println!("{}", t!("command.hosts.update.content_unchanged"));
+        return Ok(UpdateOutcome::Unchanged);
+    }
+
+    structure.add_or_update_subscription(url, &entries);
+    Ok(UpdateOutcome::Updated)
 }
@@ -0,0 +1,86 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{
+    core::HostsFileStructure,
+    helpers::{
+        create_hosts_manager, ensure_hosts_write_permission, require_subscription_target,
+        save_hosts_structure,
+    },
+    toggle::reenable_expired_subscriptions,
+};
+use crate::core::i18n::t;
+use anyhow::Result;
+
+/// 处理优先级命令：将订阅移动到指定位置（1 为最高优先级）
+pub fn handle_priority(target: &str, position: usize) -> Result<()> {
+    if position == 0 {
+        anyhow::bail!(
+            "{}",
+            t!("error.hosts_invalid_position", position = position)
+        );
+    }
+
+    reorder_subscription(target, |_, _| position - 1)
+}
+
+/// 处理移动命令：按方向调整订阅的优先级
+pub fn handle_move(target: &str, direction: &str) -> Result<()> {
+    reorder_subscription(target, |current, total| match direction {
+        "up" => current.saturating_sub(1),
+        "down" => (current + 1).min(total - 1),
+        "top" => 0,
+        _ => total - 1,
+    })
+}
+
+/// 调整订阅在配置中的顺序，并按新顺序重写 hosts 文件
+fn reorder_subscription(target: &str, new_position: impl Fn(usize, usize) -> usize) -> Result<()> {
+    // 权限检查
+    ensure_hosts_write_permission()?;
+    reenable_expired_subscriptions();
+
+    let mut config = Config::load()?;
+    let subscriptions = config.get_hosts_subscriptions();
+    let url = require_subscription_target(&config, target)?;
+
+    let current = subscriptions
+        .iter()
+        .position(|x| x == &url)
+        .unwrap_or_default();
+    let position = new_position(current, subscriptions.len()).min(subscriptions.len() - 1);
+
+    if position == current {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.priority.unchanged",
+                url = url,
+                position = position + 1
+            )
+        );
+    } else {
+        config.move_hosts_subscription(&url, position)?;
+        config.save()?;
+        println!(
+            "{}",
+            t!(
+                "command.hosts.priority.success",
+                url = url,
+                position = position + 1
+            )
+        );
+
+        // 按新的优先级重写 hosts 文件
+        let hosts_manager = create_hosts_manager()?;
+        let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+        save_hosts_structure(&hosts_manager, &mut structure)?;
+        println!("{}", t!("command.hosts.hosts_file_updated"));
+    }
+
+    println!();
+    println!("{}", t!("command.hosts.priority.current_order"));
+    for (i, sub_url) in config.get_hosts_subscriptions().iter().enumerate() {
+        println!("   {}. {}", i + 1, sub_url);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{entry::HostEntry, filter::HostnamePattern};
+use crate::core::i18n::t;
+use anyhow::Result;
+
+/// 警告中最多列出的受保护主机名数量
+const MAX_REPORTED_HOSTNAMES: usize = 10;
+
+/// 默认的受保护主机名：localhost、ip6-localhost 和本机主机名
+pub fn default_protected_hosts() -> Vec<String> {
+    let mut hosts = vec!["localhost".to_string(), "ip6-localhost".to_string()];
+    if let Ok(hostname) = nix::unistd::gethostname()
+        && let Ok(hostname) = hostname.into_string()
+    {
+        let hostname = hostname.trim().to_lowercase();
+        if !hostname.is_empty() && !hosts.contains(&hostname) {
+            hosts.push(hostname);
+        }
+    }
+    hosts
+}
+
+/// 解析受保护主机名列表（逗号或空白分隔），验证每个模式
+pub fn parse_protected_hosts(value: &str) -> Result<Vec<String>> {
+    let hosts: Vec<String> = value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|host| !host.is_empty())
+        .map(|host| host.to_string())
+        .collect();
+    for host in &hosts {
+        HostnamePattern::parse(host)?;
+    }
+    Ok(hosts)
+}
+
+/// 订阅不能覆盖的主机名
+///
+/// 模式与过滤规则相同（glob 或以 `re:` 开头的正则表达式）。
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedHosts {
+    patterns: Vec<HostnamePattern>,
+}
+
+/// 移除受保护主机名的统计结果
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedReport {
+    /// 被移除的受保护主机名（去重，保持出现顺序）
+    pub hostnames: Vec<String>,
+    /// 被移除的映射数量
+    pub dropped: usize,
+}
+
+impl ProtectedReport {
+    /// 是否移除了受保护的主机名
+    pub fn is_empty(&self) -> bool {
+        self.dropped == 0
+    }
+
+    /// 列出被移除的主机名，过多时省略其余部分
+    pub fn hostname_list(&self) -> String {
+        let mut list = self
+            .hostnames
+            .iter()
+            .take(MAX_REPORTED_HOSTNAMES)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.hostnames.len() > MAX_REPORTED_HOSTNAMES {
+            list.push_str(", ...");
+        }
+        list
+    }
+
+    /// 打印警告，没有移除任何内容时不输出
+    pub fn print(&self) {
+        if self.is_empty() {
+            return;
+        }
+        println!(
+            "{}",
+            t!(
+                "command.hosts.protected.dropped",
+                count = self.dropped,
+                hostnames = self.hostname_list()
+            )
+        );
+    }
+}
+
+impl ProtectedHosts {
+    /// 根据配置加载受保护主机名，未配置时使用默认列表
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self {
+            patterns: config
+                .protected_hosts()
+                .iter()
+                .map(|host| HostnamePattern::parse(host))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// 移除条目中的受保护主机名
+    ///
+    /// 规范名被移除时，由第一个保留的别名接替；所有主机名都被移除的条目整行丢弃。
+    pub fn apply(&self, entries: Vec<HostEntry>) -> (Vec<HostEntry>, ProtectedReport) {
+        let mut report = ProtectedReport::default();
+        if self.patterns.is_empty() {
+            return (entries, report);
+        }
+
+        let entries = entries
+            .into_iter()
+            .filter_map(|entry| {
+                let (protected, mut kept): (Vec<&str>, Vec<&str>) = entry
+                    .hostnames()
+                    .partition(|hostname| self.is_protected(hostname));
+                if protected.is_empty() {
+                    return Some(entry);
+                }
+
+                for hostname in protected {
+                    report.dropped += 1;
+                    let hostname = hostname.to_lowercase();
+                    if !report.hostnames.contains(&hostname) {
+                        report.hostnames.push(hostname);
+                    }
+                }
+                if kept.is_empty() {
+                    return None;
+                }
+                let canonical = kept.remove(0).to_string();
+                let aliases = kept.into_iter().map(|alias| alias.to_string()).collect();
+                Some(HostEntry {
+                    canonical,
+                    aliases,
+                    ..entry
+                })
+            })
+            .collect();
+
+        (entries, report)
+    }
+
+    fn is_protected(&self, hostname: &str) -> bool {
+        self.patterns
+            .iter()
+            .any(|pattern| pattern.matches(hostname))
+    }
+}
@@ -0,0 +1,232 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{entry::HostEntry, helpers::require_subscription_target};
+use crate::core::i18n::t;
+use anyhow::Result;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// 关闭重写的关键字（覆盖全局默认值）
+pub const REWRITE_OFF: &str = "off";
+
+/// 使用全局默认值的关键字
+const DEFAULT_SETTING: &str = "default";
+
+/// 指向本机的标准主机名，这些条目即使使用黑洞地址也不会被重写
+const LOCALHOST_NAMES: &[&str] = &[
+    "localhost",
+    "localhost.localdomain",
+    "localhost4",
+    "localhost4.localdomain4",
+    "localhost6",
+    "localhost6.localdomain6",
+    "ip6-localhost",
+    "ip6-loopback",
+];
+
+/// 判断 IP 是否为黑洞地址（屏蔽列表常用的 0.0.0.0、127.0.0.1、::、::1）
+///
+/// 其他回环地址（如 `127.0.1.1`）通常用于本机主机名，不算作黑洞地址。
+pub fn is_sink_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => *ip == Ipv4Addr::UNSPECIFIED || *ip == Ipv4Addr::LOCALHOST,
+        IpAddr::V6(ip) => *ip == Ipv6Addr::UNSPECIFIED || *ip == Ipv6Addr::LOCALHOST,
+    }
+}
+
+/// 判断主机名是否指向本机（`localhost` 及其常见变体，以及 `.localhost` 下的名称）
+fn is_localhost_name(hostname: &str) -> bool {
+    let hostname = hostname.to_ascii_lowercase();
+    LOCALHOST_NAMES.contains(&hostname.as_str()) || hostname.ends_with(".localhost")
+}
+
+/// 解析重写目标，`off` 表示不重写
+pub fn parse_rewrite_target(value: &str) -> Result<Option<IpAddr>> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case(REWRITE_OFF) {
+        return Ok(None);
+    }
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| anyhow::anyhow!("{}", t!("error.hosts_invalid_ip", ip = value)))
+}
+
+/// 订阅条目的 IP 重写规则
+///
+/// 只改写黑洞地址的条目，指向真实服务器的映射和包含 `localhost` 等本机主机名的条目保持不变。
+#[derive(Debug, Clone, Default)]
+pub struct IpRewrite {
+    /// 重写后的目标地址，None 表示保持原样
+    target: Option<IpAddr>,
+    /// 是否为每个 IPv4 黑洞条目额外生成对应的 IPv6 条目
+    ipv6_twin: bool,
+}
+
+/// 一次重写的统计结果
+#[derive(Debug, Clone, Default)]
+pub struct RewriteReport {
+    /// 被改写 IP 的条目数量
+    pub rewritten: usize,
+    /// 额外生成的 IPv6 条目数量
+    pub twins: usize,
+    /// 重写的目标地址
+    pub target: Option<IpAddr>,
+}
+
+impl RewriteReport {
+    /// 打印重写统计，没有任何改动时不输出
+    pub fn print(&self) {
+        if let Some(target) = self.target.filter(|_| self.rewritten > 0) {
+            println!(
+                "{}",
+                t!(
+                    "command.hosts.rewrite.rewritten",
+                    count = self.rewritten,
+                    ip = target
+                )
+            );
+        }
+        if self.twins > 0 {
+            println!(
+                "{}",
+                t!("command.hosts.rewrite.twins_added", count = self.twins)
+            );
+        }
+    }
+}
+
+impl IpRewrite {
+    /// 根据配置构建订阅的重写规则，订阅未单独设置时使用全局默认值
+    pub fn from_config(config: &Config, url: &str) -> Result<Self> {
+        let options = config.get_subscription_options(url);
+        let target = match options
+            .rewrite_ip
+            .as_deref()
+            .or(config.hosts_rewrite_ip.as_deref())
+        {
+            Some(value) => parse_rewrite_target(value)?,
+            None => None,
+        };
+
+        Ok(Self {
+            target,
+            ipv6_twin: options.ipv6_twin.unwrap_or(config.hosts_ipv6_twin),
+        })
+    }
+
+    /// 重写条目的 IP，并按需为 IPv4 黑洞条目追加 IPv6 条目
+    pub fn apply(&self, entries: Vec<HostEntry>) -> (Vec<HostEntry>, RewriteReport) {
+        let mut report = RewriteReport {
+            target: self.target,
+            ..Default::default()
+        };
+        if self.target.is_none() && !self.ipv6_twin {
+            return (entries, report);
+        }
+
+        let mut result = Vec::with_capacity(entries.len());
+        for mut entry in entries {
+            if !is_sink_ip(&entry.ip) || entry.hostnames().any(is_localhost_name) {
+                result.push(entry);
+                continue;
+            }
+
+            if let Some(target) = self.target
+                && entry.ip != target
+            {
+                entry.ip = target;
+                report.rewritten += 1;
+            }
+
+            let twin = match entry.ip {
+                IpAddr::V4(ip) if self.ipv6_twin => Some(HostEntry {
+                    ip: IpAddr::V6(ipv6_sink_for(ip)),
+                    ..entry.clone()
+                }),
+                _ => None,
+            };
+            result.push(entry);
+            if let Some(twin) = twin {
+                result.push(twin);
+                report.twins += 1;
+            }
+        }
+
+        (result, report)
+    }
+}
+
+/// IPv4 黑洞地址对应的 IPv6 地址：回环地址对应 `::1`，其余对应 `::`
+fn ipv6_sink_for(ip: Ipv4Addr) -> Ipv6Addr {
+    if ip == Ipv4Addr::LOCALHOST {
+        Ipv6Addr::LOCALHOST
+    } else {
+        Ipv6Addr::UNSPECIFIED
+    }
+}
+
+/// 处理重写设置命令
+///
+/// `ip` 为 IP 地址、`off`（不重写）或 `default`（使用全局默认值）；
+/// `ipv6_twin` 为 `on`、`off` 或 `default`。两者都未指定时显示当前设置。
+pub fn handle_rewrite(target: &str, ip: Option<&str>, ipv6_twin: Option<&str>) -> Result<()> {
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+
+    if ip.is_none() && ipv6_twin.is_none() {
+        print_rewrite_settings(&config, &url);
+        return Ok(());
+    }
+
+    // 先验证参数，避免写入无效配置
+    let rewrite_ip = match ip {
+        Some(DEFAULT_SETTING) => Some(None),
+        Some(value) => {
+            parse_rewrite_target(value)?;
+            Some(Some(value.trim().to_lowercase()))
+        }
+        None => None,
+    };
+
+    let options = config.subscription_options_mut(&url)?;
+    options.clear_fetch_state();
+    if let Some(rewrite_ip) = rewrite_ip {
+        options.rewrite_ip = rewrite_ip;
+    }
+    if let Some(twin) = ipv6_twin {
+        options.ipv6_twin = match twin {
+            DEFAULT_SETTING => None,
+            value => Some(value == "on"),
+        };
+    }
+    config.save()?;
+
+    println!("{}", t!("command.hosts.rewrite.success", url = url));
+    print_rewrite_settings(&config, &url);
+    println!("{}", t!("command.hosts.rewrite.update_hint"));
+    Ok(())
+}
+
+/// 显示订阅当前生效的重写设置
+fn print_rewrite_settings(config: &Config, url: &str) {
+    let options = config.get_subscription_options(url);
+    let inherited = t!("command.hosts.rewrite.inherited");
+
+    let rewrite_ip = match (&options.rewrite_ip, &config.hosts_rewrite_ip) {
+        (Some(value), _) => value.clone(),
+        (None, Some(value)) => format!("{value} ({inherited})"),
+        (None, None) => format!("{REWRITE_OFF} ({inherited})"),
+    };
+    let ipv6_twin = match options.ipv6_twin {
+        Some(value) => value.to_string(),
+        None => format!("{} ({inherited})", config.hosts_ipv6_twin),
+    };
+
+    println!(
+        "{}",
+        t!("command.hosts.rewrite.current_ip", value = rewrite_ip)
+    );
+    println!(
+        "{}",
+        t!("command.hosts.rewrite.current_twin", value = ipv6_twin)
+    );
+}
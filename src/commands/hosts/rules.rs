@@ -0,0 +1,70 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{
+    entry::{EntrySource, HostEntry},
+    filter::{FilterReport, HostnameFilter},
+    format::{FormatReport, ListFormat},
+    protect::{ProtectedHosts, ProtectedReport},
+    rewrite::{IpRewrite, RewriteReport},
+};
+use anyhow::Result;
+
+/// 写入订阅块前对下载条目应用的规则
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionRules {
+    pub format: ListFormat,
+    pub filter: HostnameFilter,
+    pub protect: ProtectedHosts,
+    pub rewrite: IpRewrite,
+}
+
+/// 应用订阅规则的统计结果
+#[derive(Debug, Clone, Default)]
+pub struct RulesReport {
+    pub format: FormatReport,
+    pub filter: FilterReport,
+    pub protected: ProtectedReport,
+    pub rewrite: RewriteReport,
+}
+
+impl RulesReport {
+    /// 打印各规则的统计，没有改动的规则不输出
+    pub fn print(&self) {
+        self.format.print();
+        self.filter.print();
+        self.protected.print();
+        self.rewrite.print();
+    }
+}
+
+impl SubscriptionRules {
+    /// 根据配置加载订阅的所有规则
+    pub fn from_config(config: &Config, url: &str) -> Result<Self> {
+        let options = config.get_subscription_options(url);
+        Ok(Self {
+            format: options.format,
+            filter: HostnameFilter::from_options(&options)?,
+            protect: ProtectedHosts::from_config(config)?,
+            rewrite: IpRewrite::from_config(config, url)?,
+        })
+    }
+
+    /// 按订阅格式将下载的内容转换为条目（忽略空行和注释，记录无法转换的行），并应用订阅规则
+    ///
+    /// 受保护的主机名在过滤之后、重写之前移除。
+    pub fn process(&self, url: &str, content: &str) -> (Vec<HostEntry>, RulesReport) {
+        let source = EntrySource::Subscription(url.to_string());
+        let (entries, format) = self.format.convert(content, &source);
+        let (entries, filter) = self.filter.apply(entries);
+        let (entries, protected) = self.protect.apply(entries);
+        let (entries, rewrite) = self.rewrite.apply(entries);
+        (
+            entries,
+            RulesReport {
+                format,
+                filter,
+                protected,
+                rewrite,
+            },
+        )
+    }
+}
@@ -0,0 +1,153 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::{
+    cache,
+    core::HostsFileStructure,
+    helpers::{
+        create_hosts_manager, ensure_hosts_write_permission, format_duration,
+        get_current_timestamp, parse_duration, require_subscription_target, save_hosts_structure,
+    },
+    operations::process_subscription,
+};
+use crate::core::i18n::t;
+use anyhow::Result;
+
+/// 处理禁用命令：保留订阅配置，注释掉其在 hosts 文件中的条目
+pub fn handle_disable(target: &str, duration: Option<&str>) -> Result<()> {
+    // 先解析时长，避免参数错误时做了一半的修改
+    let snooze_seconds = duration.map(parse_duration).transpose()?;
+
+    ensure_hosts_write_permission()?;
+    reenable_expired_subscriptions();
+
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+
+    let options = config.subscription_options_mut(&url)?;
+    if options.disabled && snooze_seconds.is_none() {
+        println!("{}", t!("command.hosts.disable.already", url = url));
+        return Ok(());
+    }
+    options.disabled = true;
+    options.disabled_until = snooze_seconds.map(|seconds| get_current_timestamp() + seconds);
+
+    if !set_block_enabled(&url, false)? {
+        println!("{}", t!("command.hosts.disable.block_missing"));
+    }
+    config.save()?;
+
+    println!("{}", t!("command.hosts.disable.success", url = url));
+    if let Some(seconds) = snooze_seconds {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.disable.snoozed",
+                duration = format_duration(seconds)
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// 处理启用命令：从 hosts 文件中保留的内容恢复条目，无需重新下载
+pub fn handle_enable(target: &str) -> Result<()> {
+    ensure_hosts_write_permission()?;
+    reenable_expired_subscriptions();
+
+    let mut config = Config::load()?;
+    let url = require_subscription_target(&config, target)?;
+
+    if !config.is_subscription_disabled(&url) {
+        println!("{}", t!("command.hosts.enable.already", url = url));
+        return Ok(());
+    }
+
+    enable_subscription(&mut config, &url)?;
+    config.save()?;
+
+    println!("{}", t!("command.hosts.enable.success", url = url));
+    Ok(())
+}
+
+/// 自动启用临时禁用已到期的订阅
+///
+/// 在会写入 hosts 文件的命令（非预览模式）开始时调用，只读命令和 `--dry-run` 不会触发；
+/// 没有写入权限时只给出提示，不影响当前命令。
+pub fn reenable_expired_subscriptions() {
+    let Ok(mut config) = Config::load() else {
+        return;
+    };
+
+    let now = get_current_timestamp();
+    let expired: Vec<String> = config
+        .hosts_subscriptions
+        .iter()
+        .filter(|record| {
+            record.options.disabled
+                && record
+                    .options
+                    .disabled_until
+                    .is_some_and(|until| until <= now)
+        })
+        .map(|record| record.url.clone())
+        .collect();
+
+    if expired.is_empty() {
+        return;
+    }
+
+    let result = ensure_hosts_write_permission().and_then(|()| {
+        for url in &expired {
+            enable_subscription(&mut config, url)?;
+            println!("{}", t!("command.hosts.enable.snooze_expired", url = url));
+        }
+        config.save()
+    });
+
+    if let Err(e) = result {
+        println!("{}", t!("command.hosts.enable.snooze_failed", error = e));
+    }
+}
+
+/// 在 hosts 文件和配置中启用订阅（不保存配置）
+fn enable_subscription(config: &mut Config, url: &str) -> Result<()> {
+    let options = config.subscription_options_mut(url)?;
+    options.disabled = false;
+    options.disabled_until = None;
+
+    if !set_block_enabled(url, true)? && !rebuild_block_from_cache(config, url)? {
+        println!("{}", t!("command.hosts.enable.block_missing"));
+    }
+    Ok(())
+}
+
+/// 订阅块不存在时使用缓存内容重建，没有缓存时返回 false
+fn rebuild_block_from_cache(config: &Config, url: &str) -> Result<bool> {
+    let Some(content) = cache::load_normalized(url)? else {
+        return Ok(false);
+    };
+
+    println!("{}", t!("command.hosts.cache.using_cache", url = url));
+    let entries = process_subscription(config, url, &content)?;
+    let hosts_manager = create_hosts_manager()?;
+    let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+    structure.add_or_update_subscription(url, &entries);
+
+    save_hosts_structure(&hosts_manager, &mut structure)?;
+    println!("{}", t!("command.hosts.hosts_file_updated"));
+    Ok(true)
+}
+
+/// 修改 hosts 文件中订阅块的启用状态，未找到订阅块时返回 false
+fn set_block_enabled(url: &str, enabled: bool) -> Result<bool> {
+    let hosts_manager = create_hosts_manager()?;
+    let mut structure: HostsFileStructure = hosts_manager.parse_file()?;
+
+    if !structure.set_subscription_enabled(url, enabled) {
+        return Ok(false);
+    }
+
+    save_hosts_structure(&hosts_manager, &mut structure)?;
+    println!("{}", t!("command.hosts.hosts_file_updated"));
+    Ok(true)
+}
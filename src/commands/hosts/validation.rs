@@ -1,74 +1,167 @@
+use crate::commands::config::Config;
+use crate::commands::hosts::entry::EntrySource;
+use crate::commands::hosts::format::ListFormat;
+use crate::core::globals::HOSTS_DEFAULT_MIN_VALID_RATIO;
 use crate::core::i18n::t;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
-/// 检查是否为有效的 IP 地址（IPv4 和 IPv6）
-pub fn is_valid_ip(ip: &str) -> bool {
-    // IPv4 验证
-    if ip.parse::<std::net::Ipv4Addr>().is_ok() {
-        return true;
-    }
+/// 错误信息中最多列出的行号区间数
+const MAX_REPORTED_RANGES: usize = 20;
+
+/// 下载内容的验证级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationLevel {
+    /// 有效行比例过低时只给出警告，仍然应用订阅
+    Lenient,
+    /// 有效行比例低于下限时拒绝应用订阅
+    #[default]
+    Standard,
+    /// 存在任何无效的 IP 或主机名时拒绝应用订阅
+    Strict,
+}
 
-    // IPv6 验证
-    if ip.parse::<std::net::Ipv6Addr>().is_ok() {
-        return true;
+impl ValidationLevel {
+    /// 是否为默认级别
+    pub fn is_standard(&self) -> bool {
+        *self == ValidationLevel::Standard
     }
+}
 
-    false
+impl fmt::Display for ValidationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValidationLevel::Lenient => "lenient",
+            ValidationLevel::Standard => "standard",
+            ValidationLevel::Strict => "strict",
+        };
+        write!(f, "{name}")
+    }
 }
 
-/// 验证 hosts 文件内容格式
-pub fn validate_hosts_content(content: &str) -> anyhow::Result<()> {
-    let lines: Vec<&str> = content.lines().collect();
+impl FromStr for ValidationLevel {
+    type Err = anyhow::Error;
 
-    if lines.is_empty() {
-        anyhow::bail!("{}", t!("error.hosts_content_empty"));
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "lenient" => Ok(ValidationLevel::Lenient),
+            "standard" => Ok(ValidationLevel::Standard),
+            "strict" => Ok(ValidationLevel::Strict),
+            _ => anyhow::bail!(
+                "{}",
+                t!("error.hosts_invalid_validation_level", value = value)
+            ),
+        }
     }
+}
 
-    let mut valid_lines = 0;
-    let mut total_non_empty_lines = 0;
-
-    for (line_num, line) in lines.iter().enumerate() {
-        let line = line.trim();
+/// 下载内容的验证策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValidationPolicy {
+    pub level: ValidationLevel,
+    /// 有效行占非空非注释行的最低比例（0 到 1）
+    pub min_valid_ratio: f64,
+}
 
-        // 跳过空行和注释
-        if line.is_empty() || line.starts_with('#') {
-            continue;
+impl ValidationPolicy {
+    /// 根据配置生成验证策略
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            level: config.hosts_validation,
+            min_valid_ratio: config
+                .hosts_min_valid_ratio
+                .unwrap_or(HOSTS_DEFAULT_MIN_VALID_RATIO),
         }
+    }
+}
 
-        total_non_empty_lines += 1;
-
-        // 检查是否为有效的 hosts 格式
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() >= 2 && is_valid_ip(parts[0]) {
-            valid_lines += 1;
-        } else {
-            // 允许一些无效行，但不能太多
-            if line_num < 10 {
-                // 只打印前10个错误
-                println!(
-                    "{}",
-                    t!(
-                        "command.hosts.download.validation_warning_line",
-                        line_num = line_num + 1,
-                        line = line
-                    )
-                );
-            }
-        }
+/// 按订阅格式和验证策略验证下载的内容
+///
+/// 严格模式下无法转换的行和包含无效主机名的行都会导致验证失败；
+/// 其他模式下有效行比例低于下限时，标准模式验证失败，宽松模式只给出警告。
+/// 验证失败时错误信息列出所有无效行的行号。
+/// 并发更新时各订阅的输出会交错，因此每条信息都带有订阅的 `url`。
+pub fn validate_hosts_content(
+    url: &str,
+    content: &str,
+    format: ListFormat,
+    policy: &ValidationPolicy,
+) -> Result<()> {
+    if content.lines().next().is_none() {
+        anyhow::bail!("{}", t!("error.hosts_content_empty"));
     }
 
+    let (_, report) = format.convert(content, &EntrySource::Unmanaged);
+    let valid_lines = report.converted_lines;
+    let total_non_empty_lines = valid_lines + report.skipped.len();
+
     if total_non_empty_lines == 0 {
         anyhow::bail!("{}", t!("error.hosts_content_invalid"));
     }
 
-    // 如果有效行数少于总行数的 50%，可能不是 hosts 文件
-    if valid_lines < total_non_empty_lines / 2 {
-        println!("{}", t!("command.hosts.download.validation_warning_format"));
+    if policy.level == ValidationLevel::Strict {
+        let mut invalid: Vec<usize> = report
+            .skipped
+            .iter()
+            .chain(&report.invalid_hostnames)
+            .map(|line| line.line_num)
+            .collect();
+        if !invalid.is_empty() {
+            invalid.sort_unstable();
+            anyhow::bail!(
+                "{}",
+                t!(
+                    "error.hosts_validation_strict_failed",
+                    count = invalid.len(),
+                    lines = format_line_numbers(&invalid)
+                )
+            );
+        }
+    }
+
+    let ratio = valid_lines as f64 / total_non_empty_lines as f64;
+    if ratio < policy.min_valid_ratio {
+        let invalid: Vec<usize> = report.skipped.iter().map(|line| line.line_num).collect();
+        let lines = format_line_numbers(&invalid);
+        if policy.level != ValidationLevel::Lenient {
+            anyhow::bail!(
+                "{}",
+                t!(
+                    "error.hosts_validation_ratio_failed",
+                    valid = valid_lines,
+                    total = total_non_empty_lines,
+                    min = format_percent(policy.min_valid_ratio),
+                    lines = lines
+                )
+            );
+        }
+
+        // 宽松模式：可能不是受支持的列表格式，只给出警告
+        println!(
+            "{}",
+            t!(
+                "command.hosts.download.validation_warning_format",
+                url = url
+            )
+        );
         println!(
             "{}",
             t!(
                 "command.hosts.download.validation_line_stats",
                 valid = valid_lines,
-                total = total_non_empty_lines
+                total = total_non_empty_lines,
+                url = url
+            )
+        );
+        println!(
+            "{}",
+            t!(
+                "command.hosts.download.validation_invalid_lines",
+                lines = lines,
+                url = url
             )
         );
     } else {
@@ -77,10 +170,53 @@ pub fn validate_hosts_content(content: &str) -> anyhow::Result<()> {
             t!(
                 "command.hosts.download.validation_success",
                 valid = valid_lines,
-                total = total_non_empty_lines
+                total = total_non_empty_lines,
+                url = url
             )
         );
     }
 
     Ok(())
 }
+
+/// 将升序的行号格式化为区间列表（如 `1-5, 9, 12-40`），区间过多时省略其余部分
+fn format_line_numbers(lines: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &line in lines {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+
+    let mut parts: Vec<String> = ranges
+        .iter()
+        .take(MAX_REPORTED_RANGES)
+        .map(|&(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect();
+    if ranges.len() > MAX_REPORTED_RANGES {
+        let omitted: usize = ranges[MAX_REPORTED_RANGES..]
+            .iter()
+            .map(|(start, end)| end - start + 1)
+            .sum();
+        parts.push(
+            t!(
+                "command.hosts.download.validation_more_lines",
+                count = omitted
+            )
+            .to_string(),
+        );
+    }
+    parts.join(", ")
+}
+
+/// 将比例格式化为百分比
+fn format_percent(ratio: f64) -> String {
+    format!("{}%", (ratio * 1000.0).round() / 10.0)
+}
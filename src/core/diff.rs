@@ -0,0 +1,21 @@
+//! 文本差异模块
+//!
+//! 生成统一格式（unified diff）的文本差异，用于在写入前预览文件修改。
+
+use crate::core::globals::DIFF_CONTEXT_LINES;
+use similar::TextDiff;
+
+/// 生成统一格式的差异文本，内容相同时返回 None
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> Option<String> {
+    if old == new {
+        return None;
+    }
+
+    let diff = TextDiff::from_lines(old, new);
+    Some(
+        diff.unified_diff()
+            .context_radius(DIFF_CONTEXT_LINES)
+            .header(old_label, new_label)
+            .to_string(),
+    )
+}
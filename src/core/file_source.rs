@@ -0,0 +1,117 @@
+//! 本地文件订阅源模块
+//!
+//! 支持 `file:///path` 形式的订阅 URL 和本地路径，
+//! 使用文件修改时间和内容哈希检测变化。
+
+use crate::core::globals::{FILE_PROTOCOL, HTTP_PROTOCOL, HTTPS_PROTOCOL};
+use crate::core::http::{CacheValidators, FetchResult, decode_content};
+use crate::core::i18n::t;
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 将订阅源规范化为 URL：HTTP(S) 和 `file://` URL 保持不变，
+/// 本地路径转换为绝对路径的 `file://` URL
+pub fn normalize_source(source: &str) -> Result<String> {
+    let source = source.trim();
+    if source.is_empty()
+        || source.starts_with(HTTP_PROTOCOL)
+        || source.starts_with(HTTPS_PROTOCOL)
+        || source.starts_with(FILE_PROTOCOL)
+        || source.contains("://")
+    {
+        return Ok(source.to_string());
+    }
+
+    let path = std::path::absolute(source).map_err(|e| {
+        anyhow::anyhow!(
+            "{}",
+            t!("error.hosts_local_source_failed", path = source, error = e)
+        )
+    })?;
+    Ok(format!("{FILE_PROTOCOL}{}", path.display()))
+}
+
+/// `file://` URL 对应的本地路径，其他 URL 返回 None
+pub fn file_source_path(url: &str) -> Option<PathBuf> {
+    url.strip_prefix(FILE_PROTOCOL).map(PathBuf::from)
+}
+
+/// 读取本地订阅文件
+///
+/// 修改时间与上次记录的相同，或内容哈希与上次记录的相同时返回 NotModified；
+/// 后一种情况附带新的修改时间，记录后下次无需再读取文件。
+/// 修改时间记录在 `last_modified` 中，内容哈希记录在 `etag` 中。
+/// gzip 压缩的文件（如 `.gz` 列表文件）会先解压，解压后的大小不能超过 `max_size`。
+pub fn fetch_file(path: &Path, validators: &CacheValidators, max_size: u64) -> Result<FetchResult> {
+    println!(
+        "{}",
+        t!("command.hosts.download.reading_file", path = path.display())
+    );
+
+    let read_error = |e: std::io::Error| {
+        anyhow::anyhow!(
+            "{}",
+            t!(
+                "error.hosts_local_source_failed",
+                path = path.display(),
+                error = e
+            )
+        )
+    };
+
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(read_error)?
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos().to_string())
+        .ok();
+    if modified.is_some() && validators.last_modified == modified {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.download.file_not_modified",
+                path = path.display()
+            )
+        );
+        return Ok(FetchResult::NotModified { validators: None });
+    }
+
+    let raw = fs::read(path).map_err(read_error)?;
+    let content = decode_content(&raw, &path.display().to_string(), max_size)?;
+    let hash = format!("{:x}", Sha256::digest(content.as_bytes()));
+    if validators.etag.as_deref() == Some(hash.as_str()) {
+        println!(
+            "{}",
+            t!(
+                "command.hosts.download.file_not_modified",
+                path = path.display()
+            )
+        );
+        return Ok(FetchResult::NotModified {
+            validators: Some(CacheValidators {
+                etag: Some(hash),
+                last_modified: modified,
+            }),
+        });
+    }
+
+    println!(
+        "{}",
+        t!(
+            "command.hosts.download.content_size",
+            size = content.len(),
+            url = path.display()
+        )
+    );
+    Ok(FetchResult::Modified {
+        content,
+        raw,
+        validators: CacheValidators {
+            etag: Some(hash),
+            last_modified: modified,
+        },
+    })
+}
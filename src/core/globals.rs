@@ -3,6 +3,7 @@
 //! 集中管理应用程序的全局常量、配置和状态。
 //! 按功能分类组织，便于维护和扩展。
 
+use std::path::PathBuf;
 use std::sync::OnceLock;
 
 // ============================================================================
@@ -35,6 +36,15 @@ pub const REQUIRED_ZDOCS_FILES: &[&str] = &[
     "styles.json",
 ];
 
+/// 默认的 hosts 文件路径
+pub const DEFAULT_HOSTS_FILE_PATH: &str = "/etc/hosts";
+
+/// 指定 hosts 文件路径的环境变量
+pub const HOSTS_FILE_ENV_VAR: &str = "XDEV_HOSTS_FILE";
+
+/// 命令行 `--hosts-file` 指定的 hosts 文件路径
+pub static HOSTS_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
 // ============================================================================
 // 用户界面配置
 // ============================================================================
@@ -48,6 +58,9 @@ pub const MAX_PREVIEW_LINE_LENGTH: usize = 60;
 /// 预览行截断后的显示长度
 pub const PREVIEW_LINE_DISPLAY_LENGTH: usize = 57;
 
+/// 统一差异格式的上下文行数
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
 // ============================================================================
 // 网络配置
 // ============================================================================
@@ -58,9 +71,53 @@ pub const HTTP_TIMEOUT_SECONDS: u64 = 30;
 /// HTTP 用户代理
 pub const HTTP_USER_AGENT: &str = "xdev/1.0";
 
+/// 代理地址的环境变量，按顺序取第一个非空值
+pub const HTTP_PROXY_ENV_VARS: &[&str] = &[
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+/// 不经过代理的主机列表的环境变量
+pub const HTTP_NO_PROXY_ENV_VARS: &[&str] = &["NO_PROXY", "no_proxy"];
+
+/// 额外信任的 CA 证书文件的环境变量
+pub const HTTP_CA_BUNDLE_ENV_VARS: &[&str] = &["XDEV_CA_BUNDLE", "SSL_CERT_FILE"];
+
+/// HTTP 超时时间（秒）的环境变量
+pub const HTTP_TIMEOUT_ENV_VAR: &str = "XDEV_HTTP_TIMEOUT";
+
+/// HTTP 用户代理的环境变量
+pub const HTTP_USER_AGENT_ENV_VAR: &str = "XDEV_USER_AGENT";
+
+/// 请求失败后默认的最大重试次数
+pub const HTTP_DEFAULT_RETRIES: u32 = 3;
+
+/// 首次重试前默认的等待时间（毫秒），之后每次翻倍
+pub const HTTP_RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// 重试等待时间的上限（毫秒）
+pub const HTTP_RETRY_MAX_DELAY_MS: u64 = 10_000;
+
+/// 单个订阅下载内容（解压后）默认的大小上限（MiB）
+pub const HTTP_DEFAULT_MAX_SIZE_MB: u64 = 64;
+
+/// 下载进度的输出间隔（毫秒），下载时间短于该间隔时不输出进度
+pub const HTTP_PROGRESS_INTERVAL_MS: u64 = 1_000;
+
+/// 下载内容中有效行占非空非注释行的默认最低比例
+pub const HOSTS_DEFAULT_MIN_VALID_RATIO: f64 = 0.5;
+
+/// 更新订阅时默认的并发下载数
+pub const DEFAULT_UPDATE_CONCURRENCY: usize = 4;
+
 /// 支持的 URL 协议
 pub const HTTP_PROTOCOL: &str = "http://";
 pub const HTTPS_PROTOCOL: &str = "https://";
+pub const FILE_PROTOCOL: &str = "file://";
 
 // ============================================================================
 // 配置管理
@@ -72,6 +129,9 @@ pub const BACKUP_FILE_PREFIX: &str = "hosts_backup_";
 /// 备份文件后缀
 pub const BACKUP_FILE_SUFFIX: &str = ".txt";
 
+/// 订阅内容缓存目录名（位于系统缓存目录的 xdev 子目录下）
+pub const HOSTS_CACHE_DIR_NAME: &str = "hosts";
+
 // ============================================================================
 // 应用程序配置
 // ============================================================================
@@ -84,6 +144,13 @@ pub const HOSTS_SUBSCRIPTION_START_MARKER: &str = "# === xdev hosts subscription
 pub const HOSTS_SUBSCRIPTION_END_MARKER: &str = "# === 结束 xdev hosts subscription: ";
 pub const HOSTS_SUBSCRIPTION_MARKER_SUFFIX: &str = " ===";
 
+/// 本地条目块标记
+pub const HOSTS_LOCAL_START_MARKER: &str = "# === xdev hosts local entries ===";
+pub const HOSTS_LOCAL_END_MARKER: &str = "# === 结束 xdev hosts local entries ===";
+
+/// 已禁用订阅中被注释掉的条目前缀
+pub const HOSTS_DISABLED_LINE_PREFIX: &str = "#[disabled] ";
+
 // ============================================================================
 // 语言显示名称映射
 // ============================================================================
@@ -3,68 +3,509 @@
 //! 提供通用的 HTTP 下载和网络请求功能，
 //! 可被多个命令模块复用。
 
-use crate::core::globals::{HTTP_TIMEOUT_SECONDS, HTTP_USER_AGENT};
+use crate::core::globals::{
+    HTTP_DEFAULT_MAX_SIZE_MB, HTTP_DEFAULT_RETRIES, HTTP_PROGRESS_INTERVAL_MS,
+    HTTP_RETRY_BASE_DELAY_MS, HTTP_RETRY_MAX_DELAY_MS, HTTP_TIMEOUT_SECONDS, HTTP_USER_AGENT,
+};
 use crate::core::i18n::t;
 use anyhow::Result;
-use reqwest::blocking::Client;
-use std::time::Duration;
+use flate2::read::GzDecoder;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{
+    CONTENT_TYPE, ETAG, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::{Certificate, NoProxy, Proxy, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// gzip 数据的文件头（`.gz` 列表文件，服务器未设置 `Content-Encoding` 时需要自行解压）
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// 读取响应内容时的缓冲区大小
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+/// 明显不是文本的内容类型（主类型）
+const NON_TEXT_MEDIA_TYPES: &[&str] = &["image", "audio", "video", "font", "model"];
+
+/// 明显不是文本的 `application/*` 内容类型
+const NON_TEXT_APPLICATION_TYPES: &[&str] = &[
+    "pdf",
+    "zip",
+    "x-zip-compressed",
+    "x-tar",
+    "x-7z-compressed",
+    "x-rar-compressed",
+    "vnd.rar",
+    "x-bzip2",
+    "x-xz",
+    "zstd",
+    "x-msdownload",
+    "x-executable",
+    "wasm",
+];
+
+/// 条件请求使用的缓存验证信息（来自上次响应的 `ETag` 和 `Last-Modified`）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// 下载结果
+#[derive(Debug)]
+pub enum FetchResult {
+    /// 服务器返回 304，内容自上次下载后未变化
+    ///
+    /// 内容未变化但缓存验证信息有更新时（如本地文件的修改时间变化）附带新的验证信息。
+    NotModified { validators: Option<CacheValidators> },
+    /// 下载到的内容及其缓存验证信息
+    Modified {
+        content: String,
+        /// 解压和解码前的原始字节，即发布的文件本身
+        raw: Vec<u8>,
+        validators: CacheValidators,
+    },
+}
+
+/// 请求失败时的重试策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// 首次请求失败后的最大重试次数
+    pub max_retries: u32,
+    /// 首次重试前的等待时间，之后每次翻倍
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: HTTP_DEFAULT_RETRIES,
+            base_delay: Duration::from_millis(HTTP_RETRY_BASE_DELAY_MS),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 第 `attempt` 次请求失败后的等待时间：指数退避，上限为
+    /// `HTTP_RETRY_MAX_DELAY_MS`，并加入最多一半的随机抖动，避免同时重试
+    fn delay(&self, attempt: u32) -> Duration {
+        let base = self.base_delay.as_millis() as u64;
+        let delay = base
+            .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+            .min(HTTP_RETRY_MAX_DELAY_MS);
+        let jitter = match delay / 2 {
+            0 => 0,
+            range => RandomState::new().build_hasher().finish() % range,
+        };
+        Duration::from_millis(delay - jitter)
+    }
+}
+
+/// HTTP 客户端设置
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    /// 所有请求使用的代理地址，None 时使用 reqwest 默认的系统代理检测
+    pub proxy: Option<String>,
+    /// 不经过代理的主机列表（逗号分隔，格式同 `NO_PROXY`）
+    pub no_proxy: Option<String>,
+    /// 额外信任的 CA 证书文件（PEM 格式，可包含多个证书）
+    pub ca_bundle: Option<PathBuf>,
+    /// 单个请求的超时时间
+    pub timeout: Duration,
+    /// 请求使用的用户代理
+    pub user_agent: String,
+    /// 请求失败时的重试策略
+    pub retry: RetryPolicy,
+    /// 下载内容（解压后）的大小上限（字节）
+    pub max_size: u64,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            no_proxy: None,
+            ca_bundle: None,
+            timeout: Duration::from_secs(HTTP_TIMEOUT_SECONDS),
+            user_agent: HTTP_USER_AGENT.to_string(),
+            retry: RetryPolicy::default(),
+            max_size: HTTP_DEFAULT_MAX_SIZE_MB * 1024 * 1024,
+        }
+    }
+}
+
+/// 带尝试次数的请求结果
+#[derive(Debug)]
+pub struct Attempted<T> {
+    pub value: T,
+    /// 实际发送请求的次数（包括首次请求）
+    pub attempts: u32,
+}
+
+/// 单次请求的结果
+enum Attempt<T> {
+    /// 请求完成（无需重试）
+    Done(T),
+    /// 暂时性错误（连接失败、超时、5xx 等），可以重试
+    Retry(anyhow::Error),
+}
+
+/// 是否为值得重试的状态码（5xx 和 429 Too Many Requests）
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// 内容类型是否明显不是文本（图片、音视频、压缩包、可执行文件等）
+///
+/// gzip 和 `application/octet-stream` 不视为非文本：`.gz` 列表文件会被解压，
+/// 很多服务器也用 `octet-stream` 提供纯文本的 hosts 文件。
+fn is_non_text_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let Some((media_type, subtype)) = mime.split_once('/') else {
+        return false;
+    };
+    NON_TEXT_MEDIA_TYPES.contains(&media_type)
+        || (media_type == "application" && NON_TEXT_APPLICATION_TYPES.contains(&subtype))
+}
+
+/// 将字节数格式化为易读的大小（如 `1.5 MiB`）
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KIB * KIB {
+        format!("{:.1} MiB", bytes / KIB / KIB)
+    } else {
+        format!("{:.1} KiB", bytes / KIB)
+    }
+}
+
+/// 大小超过上限时的错误
+fn too_large_error(url: &str, max_size: u64) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{}",
+        t!(
+            "error.http_response_too_large",
+            url = url,
+            limit = format_size(max_size)
+        )
+    )
+}
+
+/// 将下载的内容解码为文本
+///
+/// gzip 压缩的内容（如 `.gz` 列表文件）先解压，解压后的大小同样受 `max_size` 限制。
+/// 无效的 UTF-8 字节替换为 U+FFFD，由之后的格式验证处理。
+pub fn decode_content(bytes: &[u8], url: &str, max_size: u64) -> Result<String> {
+    if bytes.len() as u64 > max_size {
+        return Err(too_large_error(url, max_size));
+    }
+    if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decoded = Vec::new();
+        GzDecoder::new(bytes)
+            .take(max_size.saturating_add(1))
+            .read_to_end(&mut decoded)
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "{}",
+                    t!("error.http_decompress_failed", url = url, error = e)
+                )
+            })?;
+        if decoded.len() as u64 > max_size {
+            return Err(too_large_error(url, max_size));
+        }
+        return Ok(String::from_utf8_lossy(&decoded).into_owned());
+    }
+
+    Ok(String::from_utf8_lossy(bytes).into_owned())
+}
 
 /// HTTP 客户端配置和下载功能
 pub struct HttpClient {
     client: Client,
+    retry: RetryPolicy,
+    max_size: u64,
 }
 
 impl HttpClient {
-    /// 创建新的 HTTP 客户端
-    pub fn new() -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(HTTP_TIMEOUT_SECONDS)) // 使用全局配置的超时时间
-            .user_agent(HTTP_USER_AGENT)
+    /// 根据设置创建 HTTP 客户端
+    pub fn new(options: &HttpOptions) -> Result<Self> {
+        let mut builder = Client::builder()
+            .timeout(options.timeout)
+            .user_agent(&options.user_agent);
+
+        if let Some(proxy) = &options.proxy {
+            let no_proxy = options.no_proxy.as_deref().and_then(NoProxy::from_string);
+            let proxy = Proxy::all(proxy)
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "{}",
+                        t!("error.http_invalid_proxy", proxy = proxy, error = e)
+                    )
+                })?
+                .no_proxy(no_proxy);
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = &options.ca_bundle {
+            let pem = std::fs::read(path).map_err(|e| {
+                anyhow::anyhow!(
+                    "{}",
+                    t!(
+                        "error.http_ca_bundle_failed",
+                        path = path.display(),
+                        error = e
+                    )
+                )
+            })?;
+            let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| {
+                anyhow::anyhow!(
+                    "{}",
+                    t!(
+                        "error.http_ca_bundle_failed",
+                        path = path.display(),
+                        error = e
+                    )
+                )
+            })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        let client = builder
             .build()
             .map_err(|e| anyhow::anyhow!("{}", t!("error.http_client_failed", error = e)))?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            retry: options.retry,
+            max_size: options.max_size,
+        })
+    }
+
+    /// 下载内容（解压后）的大小上限（字节）
+    pub fn max_size(&self) -> u64 {
+        self.max_size
     }
 
-    /// 下载内容
-    pub fn download(&self, url: &str) -> Result<String> {
+    /// 下载内容，暂时性错误和 5xx 响应按重试策略重试
+    ///
+    /// 提供缓存验证信息时发送 `If-None-Match` / `If-Modified-Since` 条件请求，
+    /// 服务器返回 304 时不下载内容。内容以流式读取，超过大小上限时中止下载。
+    pub fn fetch(&self, url: &str, validators: &CacheValidators) -> Result<Attempted<FetchResult>> {
         println!("{}", t!("command.hosts.download.downloading", url = url));
 
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .map_err(|e| anyhow::anyhow!("{}", t!("error.hosts_download_failed", error = e)))?;
+        let fetched = self.with_retry(url, || {
+            let mut request = self.client.get(url);
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+            self.fetch_once(url, request)
+        })?;
+
+        match &fetched.value {
+            FetchResult::NotModified { .. } => {
+                println!("{}", t!("command.hosts.download.not_modified", url = url));
+            }
+            FetchResult::Modified { content, .. } => println!(
+                "{}",
+                t!(
+                    "command.hosts.download.content_size",
+                    size = content.len(),
+                    url = url
+                )
+            ),
+        }
+
+        Ok(fetched)
+    }
+
+    /// 发送一次下载请求并读取内容
+    fn fetch_once(&self, url: &str, request: RequestBuilder) -> Result<Attempt<FetchResult>> {
+        let response = match Self::send(request)? {
+            Attempt::Done(response) => response,
+            Attempt::Retry(e) => return Ok(Attempt::Retry(e)),
+        };
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Attempt::Done(FetchResult::NotModified { validators: None }));
+        }
 
         // 检查 HTTP 状态码
         if !response.status().is_success() {
+            anyhow::bail!(
+                "{}",
+                t!("error.hosts_url_not_accessible", status = response.status())
+            );
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let validators = CacheValidators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+
+        // 在读取内容前拒绝明显不是文本的内容
+        if let Some(content_type) = header(CONTENT_TYPE)
+            && is_non_text_content_type(&content_type)
+        {
             anyhow::bail!(
                 "{}",
                 t!(
-                    "error.hosts_download_http_error",
-                    status = response.status()
+                    "error.http_unsupported_content_type",
+                    url = url,
+                    content_type = content_type
                 )
             );
         }
 
-        // 获取响应内容
-        let content = response
-            .text()
-            .map_err(|e| anyhow::anyhow!("{}", t!("error.hosts_download_failed", error = e)))?;
+        let bytes = match self.read_body(url, response)? {
+            Attempt::Done(bytes) => bytes,
+            Attempt::Retry(e) => return Ok(Attempt::Retry(e)),
+        };
+        Ok(Attempt::Done(FetchResult::Modified {
+            content: decode_content(&bytes, url, self.max_size)?,
+            raw: bytes,
+            validators,
+        }))
+    }
+
+    /// 流式读取响应内容，定期输出下载进度
+    ///
+    /// 内容（或声明的 `Content-Length`）超过大小上限时失败，读取中断（如连接被重置）时重试。
+    fn read_body(&self, url: &str, mut response: Response) -> Result<Attempt<Vec<u8>>> {
+        // 压缩传输时 reqwest 不提供 Content-Length，此时总大小未知
+        let total = response.content_length();
+        if total.is_some_and(|total| total > self.max_size) {
+            return Err(too_large_error(url, self.max_size));
+        }
 
-        println!(
-            "{}",
-            t!("command.hosts.download.content_size", size = content.len())
-        );
+        let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+        let mut buffer = vec![0; READ_BUFFER_SIZE];
+        let interval = Duration::from_millis(HTTP_PROGRESS_INTERVAL_MS);
+        let mut last_progress = Instant::now();
+        loop {
+            let read = match response.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    return Ok(Attempt::Retry(anyhow::anyhow!(
+                        "{}",
+                        t!("error.hosts_download_failed", error = e)
+                    )));
+                }
+            };
+            bytes.extend_from_slice(&buffer[..read]);
+            if bytes.len() as u64 > self.max_size {
+                return Err(too_large_error(url, self.max_size));
+            }
+
+            if last_progress.elapsed() >= interval {
+                last_progress = Instant::now();
+                let downloaded = format_size(bytes.len() as u64);
+                match total {
+                    Some(total) => println!(
+                        "{}",
+                        t!(
+                            "command.hosts.download.progress",
+                            url = url,
+                            downloaded = downloaded,
+                            total = format_size(total),
+                            percent = bytes.len() as u64 * 100 / total.max(1)
+                        )
+                    ),
+                    None => println!(
+                        "{}",
+                        t!(
+                            "command.hosts.download.progress_unknown",
+                            url = url,
+                            downloaded = downloaded
+                        )
+                    ),
+                }
+            }
+        }
 
-        Ok(content)
+        Ok(Attempt::Done(bytes))
+    }
+
+    /// 发送请求，连接失败、超时和可重试的状态码视为暂时性错误
+    fn send(request: RequestBuilder) -> Result<Attempt<Response>> {
+        match request.send() {
+            Ok(response) if is_retryable_status(response.status()) => {
+                Ok(Attempt::Retry(anyhow::anyhow!(
+                    "{}",
+                    t!(
+                        "error.hosts_download_http_error",
+                        status = response.status()
+                    )
+                )))
+            }
+            Ok(response) => Ok(Attempt::Done(response)),
+            Err(e) if e.is_builder() => {
+                anyhow::bail!("{}", t!("error.hosts_download_failed", error = e))
+            }
+            Err(e) => Ok(Attempt::Retry(anyhow::anyhow!(
+                "{}",
+                t!("error.hosts_download_failed", error = e)
+            ))),
+        }
     }
 
-    /// 测试 URL 的可达性
-    pub fn test_url(&self, url: &str) -> Result<bool> {
-        match self.client.head(url).send() {
-            Ok(response) => Ok(response.status().is_success()),
-            Err(_) => Ok(false),
+    /// 按重试策略执行请求，暂时性错误时等待后重试，返回结果和尝试次数
+    fn with_retry<T>(
+        &self,
+        url: &str,
+        mut request: impl FnMut() -> Result<Attempt<T>>,
+    ) -> Result<Attempted<T>> {
+        let mut attempts = 1;
+        loop {
+            match request()? {
+                Attempt::Done(value) => return Ok(Attempted { value, attempts }),
+                Attempt::Retry(e) if attempts > self.retry.max_retries => {
+                    anyhow::bail!(
+                        "{}",
+                        t!(
+                            "error.http_retries_exhausted",
+                            attempts = attempts,
+                            error = e
+                        )
+                    );
+                }
+                Attempt::Retry(e) => {
+                    let delay = self.retry.delay(attempts);
+                    println!(
+                        "{}",
+                        t!(
+                            "command.hosts.download.retrying",
+                            url = url,
+                            attempt = attempts,
+                            error = e,
+                            delay = delay.as_millis()
+                        )
+                    );
+                    std::thread::sleep(delay);
+                    attempts += 1;
+                }
+            }
         }
     }
 }
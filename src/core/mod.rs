@@ -1,3 +1,5 @@
+pub mod diff;
+pub mod file_source;
 pub mod filesystem;
 pub mod globals;
 pub mod http;
@@ -3,7 +3,7 @@
 //! 提供通用的 URL、IP 地址和其他数据验证功能，
 //! 可被多个命令模块复用。
 
-use crate::core::globals::{HTTP_PROTOCOL, HTTPS_PROTOCOL};
+use crate::core::globals::{FILE_PROTOCOL, HTTP_PROTOCOL, HTTPS_PROTOCOL};
 use crate::core::i18n::t;
 use anyhow::Result;
 
@@ -17,6 +17,14 @@ pub fn validate_url(url: &str) -> Result<()> {
     // 简单的 URL 格式检查
     let url = url.trim();
 
+    // 本地文件只要求绝对路径（file:///path）
+    if let Some(path) = url.strip_prefix(FILE_PROTOCOL) {
+        if !path.starts_with('/') {
+            anyhow::bail!("{}", t!("error.hosts_file_url_not_absolute", url = url));
+        }
+        return Ok(());
+    }
+
     // 检查协议
     if !url.starts_with(HTTP_PROTOCOL) && !url.starts_with(HTTPS_PROTOCOL) {
         anyhow::bail!("{}", t!("error.hosts_url_invalid_protocol"));
@@ -57,6 +65,22 @@ pub fn validate_url(url: &str) -> Result<()> {
     Ok(())
 }
 
+/// 验证主机名格式
+pub fn validate_hostname(hostname: &str) -> Result<()> {
+    if !is_valid_domain_simple(hostname) {
+        anyhow::bail!(
+            "{}",
+            t!("error.hosts_invalid_hostname", hostname = hostname)
+        );
+    }
+    Ok(())
+}
+
+/// 主机名格式是否有效
+pub fn is_valid_hostname(hostname: &str) -> bool {
+    is_valid_domain_simple(hostname)
+}
+
 /// 简单的域名格式验证（内部使用）
 fn is_valid_domain_simple(domain: &str) -> bool {
     // 检查域名长度